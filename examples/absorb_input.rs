@@ -1,9 +1,6 @@
 use bevy::{
     color::palettes::{basic::PURPLE, css::YELLOW},
-    input::{
-        keyboard::KeyboardInput,
-        mouse::{MouseButtonInput, MouseWheel},
-    },
+    input::mouse::AccumulatedMouseScroll,
     prelude::*,
 };
 use bevy_egui::{
@@ -21,7 +18,9 @@ fn main() {
         // want to disable them while Egui is using input.
         //
         // As an alternative (a less safe one), you can set `EguiGlobalSettings::enable_absorb_bevy_input_system`
-        // to true to let Egui absorb all input events (see `ui_system` for the usage example).
+        // to true to let Egui reset input resources, such as `ButtonInput` (see `ui_system` for the usage example).
+        // Input messages aren't cleared, as they belong to windows: systems reading them should check the windows
+        // with the `egui_wants_pointer_input_for`, `egui_wants_keyboard_input_for` run conditions.
         .add_systems(
             Update,
             keyboard_input_system.run_if(not(egui_wants_any_keyboard_input)),
@@ -66,55 +65,36 @@ impl Default for LoremIpsum {
     }
 }
 
-#[derive(Default)]
-struct LastMessages {
-    keyboard_input: Option<KeyboardInput>,
-    mouse_button_input: Option<MouseButtonInput>,
-    mouse_wheel: Option<MouseWheel>,
-}
-
-#[allow(clippy::too_many_arguments)]
 fn ui_system(
     mut contexts: EguiContexts,
     mut egui_global_settings: ResMut<EguiGlobalSettings>,
     mut text: Local<LoremIpsum>,
-    mut last_messages: Local<LastMessages>,
-    mut keyboard_input_reader: MessageReader<KeyboardInput>,
-    mut mouse_button_input_reader: MessageReader<MouseButtonInput>,
-    mut mouse_wheel_reader: MessageReader<MouseWheel>,
+    keyboard_button_input: Res<ButtonInput<KeyCode>>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    accumulated_mouse_scroll: Res<AccumulatedMouseScroll>,
 ) -> Result {
-    if let Some(message) = keyboard_input_reader.read().last() {
-        last_messages.keyboard_input = Some(message.clone());
-    }
-    if let Some(message) = mouse_button_input_reader.read().last() {
-        last_messages.mouse_button_input = Some(*message);
-    }
-    if let Some(message) = mouse_wheel_reader.read().last() {
-        last_messages.mouse_wheel = Some(*message);
-    }
-
     egui::Window::new("Absorb Input")
         .max_size([300.0, 200.0])
         .vscroll(true)
         .show(contexts.ctx_mut()?, |ui| {
             ui.checkbox(
                 &mut egui_global_settings.enable_absorb_bevy_input_system,
-                "Absorb all input messages",
+                "Absorb input",
             );
 
             ui.separator();
 
             ui.label(format!(
-                "Last keyboard button message: {:?}",
-                last_messages.keyboard_input
+                "Pressed keys: {:?}",
+                keyboard_button_input.get_pressed().collect::<Vec<_>>()
             ));
             ui.label(format!(
-                "Last mouse button message: {:?}",
-                last_messages.mouse_button_input
+                "Pressed mouse buttons: {:?}",
+                mouse_button_input.get_pressed().collect::<Vec<_>>()
             ));
             ui.label(format!(
-                "Last mouse wheel message: {:?}",
-                last_messages.mouse_wheel
+                "Mouse scroll: {:?}",
+                accumulated_mouse_scroll.delta
             ));
 
             ui.separator();
@@ -150,7 +130,7 @@ fn pointer_input_system(
     materials: Res<Materials>,
     mesh: Single<(&mut Transform, &mut MeshMaterial2d<ColorMaterial>), Without<Camera2d>>,
     mouse_button_input: Res<ButtonInput<MouseButton>>,
-    mut mouse_wheel_reader: MessageReader<MouseWheel>,
+    accumulated_mouse_scroll: Res<AccumulatedMouseScroll>,
 ) {
     let (mut transform, mut material) = mesh.into_inner();
 
@@ -162,7 +142,5 @@ fn pointer_input_system(
         }
     }
 
-    for message in mouse_wheel_reader.read() {
-        transform.scale += message.y;
-    }
+    transform.scale += accumulated_mouse_scroll.delta.y;
 }
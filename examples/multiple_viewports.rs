@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+use bevy_egui::{
+    EguiContextSettings, EguiContexts, EguiPlugin, EguiPrimaryContextPass, PrimaryEguiContext, egui,
+};
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(EguiPlugin::default())
+        .init_resource::<ViewportsState>()
+        .add_systems(Startup, setup_camera_system)
+        .add_systems(EguiPrimaryContextPass, ui_example_system)
+        .run();
+}
+
+#[derive(Resource, Default)]
+struct ViewportsState {
+    show_immediate_viewport: bool,
+    show_deferred_viewport: Arc<AtomicBool>,
+}
+
+fn setup_camera_system(mut commands: Commands) {
+    commands.spawn((
+        Camera2d,
+        PrimaryEguiContext,
+        EguiContextSettings {
+            enable_native_viewports: true,
+            ..Default::default()
+        },
+    ));
+}
+
+fn ui_example_system(mut contexts: EguiContexts, mut state: ResMut<ViewportsState>) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    egui::Window::new("Viewports").show(ctx, |ui| {
+        ui.checkbox(
+            &mut state.show_immediate_viewport,
+            "Show immediate viewport",
+        );
+        let mut show_deferred_viewport = state.show_deferred_viewport.load(Ordering::Relaxed);
+        if ui
+            .checkbox(&mut show_deferred_viewport, "Show deferred viewport")
+            .changed()
+        {
+            state
+                .show_deferred_viewport
+                .store(show_deferred_viewport, Ordering::Relaxed);
+        }
+    });
+
+    if state.show_immediate_viewport {
+        ctx.show_viewport_immediate(
+            egui::ViewportId::from_hash_of("immediate_viewport"),
+            egui::ViewportBuilder::default()
+                .with_title("Immediate viewport")
+                .with_inner_size([300.0, 200.0]),
+            |ui, _class| {
+                egui::CentralPanel::default().show(ui, |ui| {
+                    ui.label("Hello from an immediate viewport");
+                });
+                if ui.input(|i| i.viewport().close_requested()) {
+                    state.show_immediate_viewport = false;
+                }
+            },
+        );
+    }
+
+    if state.show_deferred_viewport.load(Ordering::Relaxed) {
+        let show_deferred_viewport = state.show_deferred_viewport.clone();
+        ctx.show_viewport_deferred(
+            egui::ViewportId::from_hash_of("deferred_viewport"),
+            egui::ViewportBuilder::default()
+                .with_title("Deferred viewport")
+                .with_inner_size([300.0, 200.0]),
+            move |ui, _class| {
+                egui::CentralPanel::default().show(ui, |ui| {
+                    ui.label("Hello from a deferred viewport");
+                });
+                if ui.input(|i| i.viewport().close_requested()) {
+                    show_deferred_viewport.store(false, Ordering::Relaxed);
+                }
+            },
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,246 @@
+#[cfg(all(
+    feature = "manage_clipboard",
+    not(any(target_arch = "wasm32", target_os = "android"))
+))]
+use bevy_log as log;
+#[cfg(all(
+    feature = "manage_clipboard",
+    not(any(target_arch = "wasm32", target_os = "android"))
+))]
+use std::cell::{RefCell, RefMut};
+#[cfg(all(
+    any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ),
+    feature = "manage_clipboard"
+))]
+use std::sync::Mutex;
+
+/// A clipboard implementation that [`crate::EguiClipboard`] delegates to.
+///
+/// Implement this trait to plug in a custom clipboard (a game console clipboard, a clipboard shared over network, etc.)
+/// and install it with [`crate::EguiClipboard::new`] or [`crate::EguiClipboard::set_backend`].
+pub trait EguiClipboardBackend: std::any::Any + Send + Sync {
+    /// Places the text onto the clipboard.
+    fn set_text(&mut self, contents: &str);
+
+    /// Gets clipboard text content. Returns [`None`] if clipboard provider is unavailable or returns an error.
+    fn get_text(&mut self) -> Option<String>;
+
+    /// Places an image to the clipboard.
+    fn set_image(&mut self, image: &egui::ColorImage);
+
+    /// Gets clipboard image content. Returns [`None`] if the clipboard doesn't hold an image,
+    /// or the backend doesn't support reading images (the default implementation).
+    fn get_image(&mut self) -> Option<egui::ColorImage> {
+        None
+    }
+}
+
+/// A clipboard that stores its contents in memory, without touching the OS clipboard.
+///
+/// It's the default backend if the `manage_clipboard` feature is disabled or the platform has no supported clipboard
+/// (Android). It's also useful for headless tests and CI.
+#[derive(Clone, Debug, Default)]
+pub struct EguiInMemoryClipboard {
+    /// The last text placed onto the clipboard.
+    pub text: Option<String>,
+    /// The last image placed onto the clipboard.
+    pub image: Option<egui::ColorImage>,
+}
+
+impl EguiClipboardBackend for EguiInMemoryClipboard {
+    fn set_text(&mut self, contents: &str) {
+        self.text = Some(contents.to_owned());
+        self.image = None;
+    }
+
+    fn get_text(&mut self) -> Option<String> {
+        self.text.clone()
+    }
+
+    fn set_image(&mut self, image: &egui::ColorImage) {
+        self.text = None;
+        self.image = Some(image.clone());
+    }
+
+    fn get_image(&mut self) -> Option<egui::ColorImage> {
+        self.image.clone()
+    }
+}
+
+/// A clipboard backed by [`arboard`].
+///
+/// The default backend on desktop platforms (unless a Wayland display is detected, see [`SmithayClipboard`]).
+#[cfg(all(
+    feature = "manage_clipboard",
+    not(any(target_arch = "wasm32", target_os = "android"))
+))]
+#[derive(Default)]
+pub struct ArboardClipboard {
+    clipboard: thread_local::ThreadLocal<Option<RefCell<arboard::Clipboard>>>,
+}
+
+#[cfg(all(
+    feature = "manage_clipboard",
+    not(any(target_arch = "wasm32", target_os = "android"))
+))]
+impl ArboardClipboard {
+    fn get(&self) -> Option<RefMut<'_, arboard::Clipboard>> {
+        self.clipboard
+            .get_or(|| {
+                arboard::Clipboard::new()
+                    .map(RefCell::new)
+                    .map_err(|err| {
+                        log::error!("Failed to initialize arboard clipboard: {:?}", err);
+                    })
+                    .ok()
+            })
+            .as_ref()
+            .map(|cell| cell.borrow_mut())
+    }
+}
+
+#[cfg(all(
+    feature = "manage_clipboard",
+    not(any(target_arch = "wasm32", target_os = "android"))
+))]
+impl EguiClipboardBackend for ArboardClipboard {
+    fn set_text(&mut self, contents: &str) {
+        if let Some(mut clipboard) = self.get()
+            && let Err(err) = clipboard.set_text(contents.to_owned())
+        {
+            log::error!("Failed to set clipboard contents: {:?}", err);
+        }
+    }
+
+    fn get_text(&mut self) -> Option<String> {
+        if let Some(mut clipboard) = self.get() {
+            match clipboard.get_text() {
+                Ok(contents) => return Some(contents),
+                // We don't want to spam with this error as it usually means that the clipboard is either empty or has an incompatible format (e.g. image).
+                Err(arboard::Error::ContentNotAvailable) => return Some("".to_string()),
+                Err(err) => log::error!("Failed to get clipboard contents: {:?}", err),
+            }
+        };
+        None
+    }
+
+    fn set_image(&mut self, image: &egui::ColorImage) {
+        if let Some(mut clipboard) = self.get()
+            && let Err(err) = clipboard.set_image(arboard::ImageData {
+                width: image.width(),
+                height: image.height(),
+                bytes: std::borrow::Cow::Borrowed(bytemuck::cast_slice(&image.pixels)),
+            })
+        {
+            log::error!("Failed to set clipboard contents: {:?}", err);
+        }
+    }
+
+    fn get_image(&mut self) -> Option<egui::ColorImage> {
+        let mut clipboard = self.get()?;
+        match clipboard.get_image() {
+            Ok(image) => Some(egui::ColorImage::from_rgba_unmultiplied(
+                [image.width, image.height],
+                &image.bytes,
+            )),
+            // The clipboard is either empty or holds something other than an image.
+            Err(arboard::Error::ContentNotAvailable) => None,
+            Err(err) => {
+                log::error!("Failed to get clipboard image: {:?}", err);
+                None
+            }
+        }
+    }
+}
+
+/// A clipboard backed by [`smithay_clipboard`].
+///
+/// The plugin installs it automatically if the app runs on Wayland and the default backend hasn't been replaced.
+/// Only text is supported.
+#[cfg(all(
+    any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ),
+    feature = "manage_clipboard"
+))]
+pub struct SmithayClipboard {
+    clipboard: Mutex<smithay_clipboard::Clipboard>,
+}
+
+#[cfg(all(
+    any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ),
+    feature = "manage_clipboard"
+))]
+impl SmithayClipboard {
+    /// Creates a clipboard for the given Wayland display.
+    ///
+    /// # Safety
+    ///
+    /// `display` must be a valid pointer to a Wayland `wl_display` that outlives the clipboard.
+    pub unsafe fn new(display: *mut std::ffi::c_void) -> Self {
+        Self {
+            // Safety: upheld by the caller.
+            clipboard: Mutex::new(unsafe { smithay_clipboard::Clipboard::new(display) }),
+        }
+    }
+}
+
+#[cfg(all(
+    any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ),
+    feature = "manage_clipboard"
+))]
+impl EguiClipboardBackend for SmithayClipboard {
+    fn set_text(&mut self, contents: &str) {
+        self.clipboard.lock().unwrap().store(contents);
+    }
+
+    fn get_text(&mut self) -> Option<String> {
+        match self.clipboard.lock().unwrap().load() {
+            Ok(text) => Some(text),
+            Err(err) => {
+                log::error!("Failed to get clipboard content via smithay: {err}");
+                None
+            }
+        }
+    }
+
+    fn set_image(&mut self, _image: &egui::ColorImage) {
+        log::error!("Setting image to clipboard via smithay is not supported");
+    }
+}
+
+/// Creates the backend that [`crate::EguiClipboard`] uses by default on the current platform.
+pub(crate) fn default_backend() -> Box<dyn EguiClipboardBackend> {
+    #[cfg(all(
+        feature = "manage_clipboard",
+        not(any(target_arch = "wasm32", target_os = "android"))
+    ))]
+    let backend = ArboardClipboard::default();
+    #[cfg(all(feature = "manage_clipboard", target_arch = "wasm32"))]
+    let backend = crate::web_clipboard::WebClipboard::default();
+    #[cfg(not(all(feature = "manage_clipboard", not(target_os = "android"))))]
+    let backend = EguiInMemoryClipboard::default();
+    Box::new(backend)
+}
@@ -44,155 +44,278 @@ pub fn egui_to_winit_cursor_icon(
     }
 }
 
-/// Matches the implementation of <https://github.com/emilk/egui/blob/68b3ef7f6badfe893d3bbb1f791b481069d807d9/crates/egui-winit/src/lib.rs#L1005>.
+/// Translates [`egui::ResizeDirection`] into [`bevy_math::CompassOctant`].
+#[inline(always)]
+pub fn egui_to_bevy_resize_direction(direction: egui::ResizeDirection) -> bevy_math::CompassOctant {
+    match direction {
+        egui::ResizeDirection::North => bevy_math::CompassOctant::North,
+        egui::ResizeDirection::South => bevy_math::CompassOctant::South,
+        egui::ResizeDirection::East => bevy_math::CompassOctant::East,
+        egui::ResizeDirection::West => bevy_math::CompassOctant::West,
+        egui::ResizeDirection::NorthEast => bevy_math::CompassOctant::NorthEast,
+        egui::ResizeDirection::SouthEast => bevy_math::CompassOctant::SouthEast,
+        egui::ResizeDirection::NorthWest => bevy_math::CompassOctant::NorthWest,
+        egui::ResizeDirection::SouthWest => bevy_math::CompassOctant::SouthWest,
+    }
+}
+
+/// Translates [`egui::WindowLevel`] into [`bevy_window::WindowLevel`].
+#[inline(always)]
+pub fn egui_to_bevy_window_level(level: egui::WindowLevel) -> bevy_window::WindowLevel {
+    match level {
+        egui::WindowLevel::Normal => bevy_window::WindowLevel::Normal,
+        egui::WindowLevel::AlwaysOnBottom => bevy_window::WindowLevel::AlwaysOnBottom,
+        egui::WindowLevel::AlwaysOnTop => bevy_window::WindowLevel::AlwaysOnTop,
+    }
+}
+
+/// Translates [`egui::CursorGrab`] into [`bevy_window::CursorGrabMode`].
+#[inline(always)]
+pub fn egui_to_bevy_cursor_grab_mode(grab: egui::CursorGrab) -> bevy_window::CursorGrabMode {
+    match grab {
+        egui::CursorGrab::None => bevy_window::CursorGrabMode::None,
+        egui::CursorGrab::Confined => bevy_window::CursorGrabMode::Confined,
+        egui::CursorGrab::Locked => bevy_window::CursorGrabMode::Locked,
+    }
+}
+
+/// Translates [`egui::SystemTheme`] into [`bevy_window::WindowTheme`],
+/// [`egui::SystemTheme::SystemDefault`] maps to [`None`].
+#[inline(always)]
+pub fn egui_to_bevy_window_theme(theme: egui::SystemTheme) -> Option<bevy_window::WindowTheme> {
+    match theme {
+        egui::SystemTheme::SystemDefault => None,
+        egui::SystemTheme::Light => Some(bevy_window::WindowTheme::Light),
+        egui::SystemTheme::Dark => Some(bevy_window::WindowTheme::Dark),
+    }
+}
+
+/// Translates [`bevy_window::WindowTheme`] into [`egui::Theme`].
+#[inline(always)]
+pub fn bevy_to_egui_theme(theme: bevy_window::WindowTheme) -> egui::Theme {
+    match theme {
+        bevy_window::WindowTheme::Light => egui::Theme::Light,
+        bevy_window::WindowTheme::Dark => egui::Theme::Dark,
+    }
+}
+
+/// Named [`Key`]s and the [`egui::Key`]s they translate into, see [`bevy_to_egui_key`].
+///
+/// The table follows the key names accepted by [`egui::Key::from_name`].
+pub const LOGICAL_KEY_MAP: &[(Key, egui::Key)] = &[
+    (Key::Enter, egui::Key::Enter),
+    (Key::Tab, egui::Key::Tab),
+    (Key::Space, egui::Key::Space),
+    (Key::ArrowDown, egui::Key::ArrowDown),
+    (Key::ArrowLeft, egui::Key::ArrowLeft),
+    (Key::ArrowRight, egui::Key::ArrowRight),
+    (Key::ArrowUp, egui::Key::ArrowUp),
+    (Key::End, egui::Key::End),
+    (Key::Home, egui::Key::Home),
+    (Key::PageDown, egui::Key::PageDown),
+    (Key::PageUp, egui::Key::PageUp),
+    (Key::Backspace, egui::Key::Backspace),
+    (Key::Delete, egui::Key::Delete),
+    (Key::Insert, egui::Key::Insert),
+    (Key::Help, egui::Key::Insert),
+    (Key::Escape, egui::Key::Escape),
+    (Key::Copy, egui::Key::Copy),
+    (Key::Cut, egui::Key::Cut),
+    (Key::Paste, egui::Key::Paste),
+    (Key::BrowserBack, egui::Key::BrowserBack),
+    (Key::F1, egui::Key::F1),
+    (Key::F2, egui::Key::F2),
+    (Key::F3, egui::Key::F3),
+    (Key::F4, egui::Key::F4),
+    (Key::F5, egui::Key::F5),
+    (Key::F6, egui::Key::F6),
+    (Key::F7, egui::Key::F7),
+    (Key::F8, egui::Key::F8),
+    (Key::F9, egui::Key::F9),
+    (Key::F10, egui::Key::F10),
+    (Key::F11, egui::Key::F11),
+    (Key::F12, egui::Key::F12),
+    (Key::F13, egui::Key::F13),
+    (Key::F14, egui::Key::F14),
+    (Key::F15, egui::Key::F15),
+    (Key::F16, egui::Key::F16),
+    (Key::F17, egui::Key::F17),
+    (Key::F18, egui::Key::F18),
+    (Key::F19, egui::Key::F19),
+    (Key::F20, egui::Key::F20),
+    (Key::F21, egui::Key::F21),
+    (Key::F22, egui::Key::F22),
+    (Key::F23, egui::Key::F23),
+    (Key::F24, egui::Key::F24),
+    (Key::F25, egui::Key::F25),
+    (Key::F26, egui::Key::F26),
+    (Key::F27, egui::Key::F27),
+    (Key::F28, egui::Key::F28),
+    (Key::F29, egui::Key::F29),
+    (Key::F30, egui::Key::F30),
+    (Key::F31, egui::Key::F31),
+    (Key::F32, egui::Key::F32),
+    (Key::F33, egui::Key::F33),
+    (Key::F34, egui::Key::F34),
+    (Key::F35, egui::Key::F35),
+];
+
+/// [`KeyCode`]s and the [`egui::Key`]s they translate into, see [`bevy_to_egui_physical_key`].
+///
+/// The table follows the key names accepted by [`egui::Key::from_name`]
+/// (which match the names of [`KeyCode`] variants).
+pub const PHYSICAL_KEY_MAP: &[(KeyCode, egui::Key)] = &[
+    (KeyCode::ArrowDown, egui::Key::ArrowDown),
+    (KeyCode::ArrowLeft, egui::Key::ArrowLeft),
+    (KeyCode::ArrowRight, egui::Key::ArrowRight),
+    (KeyCode::ArrowUp, egui::Key::ArrowUp),
+    (KeyCode::Escape, egui::Key::Escape),
+    (KeyCode::Tab, egui::Key::Tab),
+    (KeyCode::Backspace, egui::Key::Backspace),
+    (KeyCode::Enter, egui::Key::Enter),
+    (KeyCode::NumpadEnter, egui::Key::Enter),
+    (KeyCode::Insert, egui::Key::Insert),
+    (KeyCode::Help, egui::Key::Insert),
+    (KeyCode::Delete, egui::Key::Delete),
+    (KeyCode::Home, egui::Key::Home),
+    (KeyCode::End, egui::Key::End),
+    (KeyCode::PageUp, egui::Key::PageUp),
+    (KeyCode::PageDown, egui::Key::PageDown),
+    (KeyCode::Copy, egui::Key::Copy),
+    (KeyCode::Cut, egui::Key::Cut),
+    (KeyCode::Paste, egui::Key::Paste),
+    (KeyCode::Space, egui::Key::Space),
+    (KeyCode::Comma, egui::Key::Comma),
+    (KeyCode::NumpadComma, egui::Key::Comma),
+    (KeyCode::Period, egui::Key::Period),
+    (KeyCode::NumpadDecimal, egui::Key::Period),
+    (KeyCode::Semicolon, egui::Key::Semicolon),
+    (KeyCode::Backslash, egui::Key::Backslash),
+    (KeyCode::Slash, egui::Key::Slash),
+    (KeyCode::NumpadDivide, egui::Key::Slash),
+    (KeyCode::BracketLeft, egui::Key::OpenBracket),
+    (KeyCode::BracketRight, egui::Key::CloseBracket),
+    (KeyCode::Backquote, egui::Key::Backtick),
+    (KeyCode::Quote, egui::Key::Quote),
+    (KeyCode::Minus, egui::Key::Minus),
+    (KeyCode::NumpadSubtract, egui::Key::Minus),
+    (KeyCode::NumpadAdd, egui::Key::Plus),
+    (KeyCode::Equal, egui::Key::Equals),
+    (KeyCode::NumpadEqual, egui::Key::Equals),
+    (KeyCode::Digit0, egui::Key::Num0),
+    (KeyCode::Numpad0, egui::Key::Num0),
+    (KeyCode::Digit1, egui::Key::Num1),
+    (KeyCode::Numpad1, egui::Key::Num1),
+    (KeyCode::Digit2, egui::Key::Num2),
+    (KeyCode::Numpad2, egui::Key::Num2),
+    (KeyCode::Digit3, egui::Key::Num3),
+    (KeyCode::Numpad3, egui::Key::Num3),
+    (KeyCode::Digit4, egui::Key::Num4),
+    (KeyCode::Numpad4, egui::Key::Num4),
+    (KeyCode::Digit5, egui::Key::Num5),
+    (KeyCode::Numpad5, egui::Key::Num5),
+    (KeyCode::Digit6, egui::Key::Num6),
+    (KeyCode::Numpad6, egui::Key::Num6),
+    (KeyCode::Digit7, egui::Key::Num7),
+    (KeyCode::Numpad7, egui::Key::Num7),
+    (KeyCode::Digit8, egui::Key::Num8),
+    (KeyCode::Numpad8, egui::Key::Num8),
+    (KeyCode::Digit9, egui::Key::Num9),
+    (KeyCode::Numpad9, egui::Key::Num9),
+    (KeyCode::KeyA, egui::Key::A),
+    (KeyCode::KeyB, egui::Key::B),
+    (KeyCode::KeyC, egui::Key::C),
+    (KeyCode::KeyD, egui::Key::D),
+    (KeyCode::KeyE, egui::Key::E),
+    (KeyCode::KeyF, egui::Key::F),
+    (KeyCode::KeyG, egui::Key::G),
+    (KeyCode::KeyH, egui::Key::H),
+    (KeyCode::KeyI, egui::Key::I),
+    (KeyCode::KeyJ, egui::Key::J),
+    (KeyCode::KeyK, egui::Key::K),
+    (KeyCode::KeyL, egui::Key::L),
+    (KeyCode::KeyM, egui::Key::M),
+    (KeyCode::KeyN, egui::Key::N),
+    (KeyCode::KeyO, egui::Key::O),
+    (KeyCode::KeyP, egui::Key::P),
+    (KeyCode::KeyQ, egui::Key::Q),
+    (KeyCode::KeyR, egui::Key::R),
+    (KeyCode::KeyS, egui::Key::S),
+    (KeyCode::KeyT, egui::Key::T),
+    (KeyCode::KeyU, egui::Key::U),
+    (KeyCode::KeyV, egui::Key::V),
+    (KeyCode::KeyW, egui::Key::W),
+    (KeyCode::KeyX, egui::Key::X),
+    (KeyCode::KeyY, egui::Key::Y),
+    (KeyCode::KeyZ, egui::Key::Z),
+    (KeyCode::F1, egui::Key::F1),
+    (KeyCode::F2, egui::Key::F2),
+    (KeyCode::F3, egui::Key::F3),
+    (KeyCode::F4, egui::Key::F4),
+    (KeyCode::F5, egui::Key::F5),
+    (KeyCode::F6, egui::Key::F6),
+    (KeyCode::F7, egui::Key::F7),
+    (KeyCode::F8, egui::Key::F8),
+    (KeyCode::F9, egui::Key::F9),
+    (KeyCode::F10, egui::Key::F10),
+    (KeyCode::F11, egui::Key::F11),
+    (KeyCode::F12, egui::Key::F12),
+    (KeyCode::F13, egui::Key::F13),
+    (KeyCode::F14, egui::Key::F14),
+    (KeyCode::F15, egui::Key::F15),
+    (KeyCode::F16, egui::Key::F16),
+    (KeyCode::F17, egui::Key::F17),
+    (KeyCode::F18, egui::Key::F18),
+    (KeyCode::F19, egui::Key::F19),
+    (KeyCode::F20, egui::Key::F20),
+    (KeyCode::F21, egui::Key::F21),
+    (KeyCode::F22, egui::Key::F22),
+    (KeyCode::F23, egui::Key::F23),
+    (KeyCode::F24, egui::Key::F24),
+    (KeyCode::F25, egui::Key::F25),
+    (KeyCode::F26, egui::Key::F26),
+    (KeyCode::F27, egui::Key::F27),
+    (KeyCode::F28, egui::Key::F28),
+    (KeyCode::F29, egui::Key::F29),
+    (KeyCode::F30, egui::Key::F30),
+    (KeyCode::F31, egui::Key::F31),
+    (KeyCode::F32, egui::Key::F32),
+    (KeyCode::F33, egui::Key::F33),
+    (KeyCode::F34, egui::Key::F34),
+    (KeyCode::F35, egui::Key::F35),
+    (KeyCode::BrowserBack, egui::Key::BrowserBack),
+    (KeyCode::ShiftLeft, egui::Key::ShiftLeft),
+    (KeyCode::ShiftRight, egui::Key::ShiftRight),
+    (KeyCode::ControlLeft, egui::Key::ControlLeft),
+    (KeyCode::ControlRight, egui::Key::ControlRight),
+    (KeyCode::AltLeft, egui::Key::AltLeft),
+    (KeyCode::AltRight, egui::Key::AltRight),
+    (KeyCode::SuperLeft, egui::Key::SuperLeft),
+    (KeyCode::SuperRight, egui::Key::SuperRight),
+    (KeyCode::IntlBackslash, egui::Key::IntlBackslash),
+];
+
+/// Translates a logical [`Key`] into [`egui::Key`], using [`LOGICAL_KEY_MAP`] for named keys.
+///
+/// Returns [`None`] for keys that Egui doesn't have a counterpart for (including [`Key::Unidentified`] and [`Key::Dead`]),
+/// [`bevy_to_egui_physical_key`] can be used as a fallback.
 #[inline(always)]
 pub fn bevy_to_egui_key(key: &Key) -> Option<egui::Key> {
-    let key = match key {
-        Key::Character(str) => return egui::Key::from_name(str.as_str()),
-        Key::Unidentified(_) | Key::Dead(_) => return None,
-
-        Key::Enter => egui::Key::Enter,
-        Key::Tab => egui::Key::Tab,
-        Key::Space => egui::Key::Space,
-        Key::ArrowDown => egui::Key::ArrowDown,
-        Key::ArrowLeft => egui::Key::ArrowLeft,
-        Key::ArrowRight => egui::Key::ArrowRight,
-        Key::ArrowUp => egui::Key::ArrowUp,
-        Key::End => egui::Key::End,
-        Key::Home => egui::Key::Home,
-        Key::PageDown => egui::Key::PageDown,
-        Key::PageUp => egui::Key::PageUp,
-        Key::Backspace => egui::Key::Backspace,
-        Key::Delete => egui::Key::Delete,
-        Key::Insert => egui::Key::Insert,
-        Key::Escape => egui::Key::Escape,
-        Key::F1 => egui::Key::F1,
-        Key::F2 => egui::Key::F2,
-        Key::F3 => egui::Key::F3,
-        Key::F4 => egui::Key::F4,
-        Key::F5 => egui::Key::F5,
-        Key::F6 => egui::Key::F6,
-        Key::F7 => egui::Key::F7,
-        Key::F8 => egui::Key::F8,
-        Key::F9 => egui::Key::F9,
-        Key::F10 => egui::Key::F10,
-        Key::F11 => egui::Key::F11,
-        Key::F12 => egui::Key::F12,
-        Key::F13 => egui::Key::F13,
-        Key::F14 => egui::Key::F14,
-        Key::F15 => egui::Key::F15,
-        Key::F16 => egui::Key::F16,
-        Key::F17 => egui::Key::F17,
-        Key::F18 => egui::Key::F18,
-        Key::F19 => egui::Key::F19,
-        Key::F20 => egui::Key::F20,
-
-        _ => return None,
-    };
-    Some(key)
+    match key {
+        Key::Character(str) => egui::Key::from_name(str.as_str()),
+        Key::Unidentified(_) | Key::Dead(_) => None,
+        key => LOGICAL_KEY_MAP
+            .iter()
+            .find_map(|(bevy_key, egui_key)| (bevy_key == key).then_some(*egui_key)),
+    }
 }
 
-/// Matches the implementation of <https://github.com/emilk/egui/blob/68b3ef7f6badfe893d3bbb1f791b481069d807d9/crates/egui-winit/src/lib.rs#L1080>.
+/// Translates a physical [`KeyCode`] into [`egui::Key`], using [`PHYSICAL_KEY_MAP`].
 #[inline(always)]
 pub fn bevy_to_egui_physical_key(key: &KeyCode) -> Option<egui::Key> {
-    let key = match key {
-        KeyCode::ArrowDown => egui::Key::ArrowDown,
-        KeyCode::ArrowLeft => egui::Key::ArrowLeft,
-        KeyCode::ArrowRight => egui::Key::ArrowRight,
-        KeyCode::ArrowUp => egui::Key::ArrowUp,
-
-        KeyCode::Escape => egui::Key::Escape,
-        KeyCode::Tab => egui::Key::Tab,
-        KeyCode::Backspace => egui::Key::Backspace,
-        KeyCode::Enter | KeyCode::NumpadEnter => egui::Key::Enter,
-
-        KeyCode::Insert => egui::Key::Insert,
-        KeyCode::Delete => egui::Key::Delete,
-        KeyCode::Home => egui::Key::Home,
-        KeyCode::End => egui::Key::End,
-        KeyCode::PageUp => egui::Key::PageUp,
-        KeyCode::PageDown => egui::Key::PageDown,
-
-        // Punctuation
-        KeyCode::Space => egui::Key::Space,
-        KeyCode::Comma => egui::Key::Comma,
-        KeyCode::Period => egui::Key::Period,
-        // KeyCode::Colon => egui::Key::Colon, // NOTE: there is no physical colon key on an american keyboard
-        KeyCode::Semicolon => egui::Key::Semicolon,
-        KeyCode::Backslash => egui::Key::Backslash,
-        KeyCode::Slash | KeyCode::NumpadDivide => egui::Key::Slash,
-        KeyCode::BracketLeft => egui::Key::OpenBracket,
-        KeyCode::BracketRight => egui::Key::CloseBracket,
-        KeyCode::Backquote => egui::Key::Backtick,
-
-        KeyCode::Cut => egui::Key::Cut,
-        KeyCode::Copy => egui::Key::Copy,
-        KeyCode::Paste => egui::Key::Paste,
-        KeyCode::Minus | KeyCode::NumpadSubtract => egui::Key::Minus,
-        KeyCode::NumpadAdd => egui::Key::Plus,
-        KeyCode::Equal => egui::Key::Equals,
-
-        KeyCode::Digit0 | KeyCode::Numpad0 => egui::Key::Num0,
-        KeyCode::Digit1 | KeyCode::Numpad1 => egui::Key::Num1,
-        KeyCode::Digit2 | KeyCode::Numpad2 => egui::Key::Num2,
-        KeyCode::Digit3 | KeyCode::Numpad3 => egui::Key::Num3,
-        KeyCode::Digit4 | KeyCode::Numpad4 => egui::Key::Num4,
-        KeyCode::Digit5 | KeyCode::Numpad5 => egui::Key::Num5,
-        KeyCode::Digit6 | KeyCode::Numpad6 => egui::Key::Num6,
-        KeyCode::Digit7 | KeyCode::Numpad7 => egui::Key::Num7,
-        KeyCode::Digit8 | KeyCode::Numpad8 => egui::Key::Num8,
-        KeyCode::Digit9 | KeyCode::Numpad9 => egui::Key::Num9,
-
-        KeyCode::KeyA => egui::Key::A,
-        KeyCode::KeyB => egui::Key::B,
-        KeyCode::KeyC => egui::Key::C,
-        KeyCode::KeyD => egui::Key::D,
-        KeyCode::KeyE => egui::Key::E,
-        KeyCode::KeyF => egui::Key::F,
-        KeyCode::KeyG => egui::Key::G,
-        KeyCode::KeyH => egui::Key::H,
-        KeyCode::KeyI => egui::Key::I,
-        KeyCode::KeyJ => egui::Key::J,
-        KeyCode::KeyK => egui::Key::K,
-        KeyCode::KeyL => egui::Key::L,
-        KeyCode::KeyM => egui::Key::M,
-        KeyCode::KeyN => egui::Key::N,
-        KeyCode::KeyO => egui::Key::O,
-        KeyCode::KeyP => egui::Key::P,
-        KeyCode::KeyQ => egui::Key::Q,
-        KeyCode::KeyR => egui::Key::R,
-        KeyCode::KeyS => egui::Key::S,
-        KeyCode::KeyT => egui::Key::T,
-        KeyCode::KeyU => egui::Key::U,
-        KeyCode::KeyV => egui::Key::V,
-        KeyCode::KeyW => egui::Key::W,
-        KeyCode::KeyX => egui::Key::X,
-        KeyCode::KeyY => egui::Key::Y,
-        KeyCode::KeyZ => egui::Key::Z,
-
-        KeyCode::F1 => egui::Key::F1,
-        KeyCode::F2 => egui::Key::F2,
-        KeyCode::F3 => egui::Key::F3,
-        KeyCode::F4 => egui::Key::F4,
-        KeyCode::F5 => egui::Key::F5,
-        KeyCode::F6 => egui::Key::F6,
-        KeyCode::F7 => egui::Key::F7,
-        KeyCode::F8 => egui::Key::F8,
-        KeyCode::F9 => egui::Key::F9,
-        KeyCode::F10 => egui::Key::F10,
-        KeyCode::F11 => egui::Key::F11,
-        KeyCode::F12 => egui::Key::F12,
-        KeyCode::F13 => egui::Key::F13,
-        KeyCode::F14 => egui::Key::F14,
-        KeyCode::F15 => egui::Key::F15,
-        KeyCode::F16 => egui::Key::F16,
-        KeyCode::F17 => egui::Key::F17,
-        KeyCode::F18 => egui::Key::F18,
-        KeyCode::F19 => egui::Key::F19,
-        KeyCode::F20 => egui::Key::F20,
-        _ => return None,
-    };
-    Some(key)
+    PHYSICAL_KEY_MAP
+        .iter()
+        .find_map(|(key_code, egui_key)| (key_code == key).then_some(*egui_key))
 }
 
 /// Converts [`bevy_math::Vec2`] into [`egui::Pos2`].
@@ -236,3 +359,59 @@ pub fn egui_rect_into_rect(rect: egui::Rect) -> bevy_math::Rect {
         max: egui_pos2_into_vec2(rect.max),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_reflect::{
+        FromReflect, TypeInfo, Typed,
+        enums::{DynamicEnum, DynamicVariant, VariantInfo},
+    };
+
+    /// Returns all unit variants of an enum with their names.
+    fn unit_variants<T: Typed + FromReflect>() -> Vec<(&'static str, T)> {
+        let TypeInfo::Enum(enum_info) = T::type_info() else {
+            panic!("{} isn't an enum", T::type_path());
+        };
+        enum_info
+            .iter()
+            .filter_map(|variant| match variant {
+                VariantInfo::Unit(variant) => Some(variant.name()),
+                _ => None,
+            })
+            .map(|name| {
+                let value = T::from_reflect(&DynamicEnum::new(name, DynamicVariant::Unit))
+                    .expect("failed to construct a unit variant");
+                (name, value)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn physical_keys_match_egui_key_names() {
+        let key_codes = unit_variants::<KeyCode>();
+        assert!(key_codes.len() > 150);
+        for (name, key_code) in key_codes {
+            assert_eq!(
+                bevy_to_egui_physical_key(&key_code),
+                egui::Key::from_name(name),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn logical_keys_match_egui_key_names() {
+        let keys = unit_variants::<Key>();
+        assert!(keys.len() > 250);
+        for (name, key) in keys {
+            assert_eq!(bevy_to_egui_key(&key), egui::Key::from_name(name), "{name}");
+        }
+        assert_eq!(
+            bevy_to_egui_key(&Key::Character("a".into())),
+            Some(egui::Key::A)
+        );
+        assert_eq!(bevy_to_egui_key(&Key::Character("ä".into())), None);
+        assert_eq!(bevy_to_egui_key(&Key::Dead(Some('^'))), None);
+    }
+}
@@ -1,10 +1,14 @@
 #[cfg(target_arch = "wasm32")]
 use crate::text_agent::{is_mobile_safari, update_text_agent};
 use crate::{
-    EguiContext, EguiContextSettings, EguiGlobalSettings, EguiInput, EguiOutput, EguiZoomFactor,
-    helpers::vec2_into_egui_pos2,
+    EguiContext, EguiContextSettings, EguiGlobalSettings, EguiInput, EguiInputSystemSettings,
+    EguiLongPressSettings, EguiOutput, EguiZoomFactor,
+    helpers::{bevy_to_egui_theme, vec2_into_egui_pos2},
+    output::EguiRepaintScheduler,
+    virtual_cursor::EguiGamepadVirtualCursor,
 };
 use bevy_camera::Camera;
+use bevy_derive::Deref;
 use bevy_ecs::{
     message::MessageIterator,
     prelude::*,
@@ -12,16 +16,28 @@ use bevy_ecs::{
 };
 use bevy_input::{
     ButtonInput, ButtonState,
-    gestures::PinchGesture,
+    gamepad::{
+        Gamepad, GamepadAxisChangedEvent, GamepadButton, GamepadButtonChangedEvent,
+        GamepadButtonStateChangedEvent,
+    },
+    gestures::{DoubleTapGesture, PanGesture, PinchGesture, RotationGesture},
     keyboard::{Key, KeyCode, KeyboardFocusLost, KeyboardInput},
-    mouse::{MouseButton, MouseButtonInput, MouseScrollUnit, MouseWheel},
-    touch::TouchInput,
+    mouse::{
+        AccumulatedMouseMotion, AccumulatedMouseScroll, MouseButton, MouseMotion, MouseScrollUnit,
+        MouseWheel,
+    },
+    touch::{TouchInput, Touches},
 };
 use bevy_log::{self as log};
+use bevy_math::Vec2;
+use bevy_platform::collections::{HashMap, HashSet};
 use bevy_time::{Real, Time};
-use bevy_window::{CursorMoved, FileDragAndDrop, Ime, Window, WindowEvent};
+use bevy_window::{
+    CursorMoved, FileDragAndDrop, Ime, Monitor, OnMonitor, Window, WindowEvent, WindowFocused,
+    WindowMode, WindowOccluded, WindowPosition, WindowTheme, WindowThemeChanged,
+};
 use bevy_winit::WinitUserEvent;
-use egui::{Modifiers, TouchPhase, ViewportId};
+use egui::{Modifiers, TouchPhase};
 
 /// A [`egui::DroppedFile`], dropped via [`bevy_window::FileDragAndDrop`].
 #[derive(Debug)]
@@ -56,11 +72,50 @@ pub struct EguiContextPointerPosition {
     pub position: egui::Pos2,
 }
 
-/// Stores an active touch id.
+/// Stores an active touch id and positions of all touches, used for pointer emulation and two-finger scrolling.
 #[derive(Component, Default)]
 pub struct EguiContextPointerTouchId {
     /// Active touch id.
     pub pointer_touch_id: Option<u64>,
+    /// Positions of all active touches.
+    pub touch_positions: HashMap<u64, egui::Pos2>,
+    /// Position where the emulated pointer stopped following the active touch until it ends:
+    /// when another touch started (more than one touch scrolls instead), or after a long press.
+    pub frozen_pointer_position: Option<egui::Pos2>,
+    /// Time ([`Time<Real>::elapsed`]) and position of the active touch start,
+    /// while it can still become a long press (see [`crate::EguiContextSettings::long_press`]).
+    pub long_press_start: Option<(std::time::Duration, egui::Pos2)>,
+}
+
+/// Stores the pressure and tilt of the touch (a finger or a stylus) that emulates the pointer of a context.
+///
+/// It's updated by [`write_window_touch_messages_system`] and [`write_non_window_touch_messages_system`],
+/// and is also available inside Egui code via [`EguiPenState::from_ctx`].
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct EguiPenState {
+    /// Whether the touch is currently pressed.
+    pub touching: bool,
+    /// Pressure from `0.0` to `1.0`, [`None`] if the device doesn't report it.
+    pub pressure: Option<f32>,
+    /// Altitude of the stylus in radians: `0.0` if it's parallel to the surface, `PI / 2` if it's perpendicular.
+    /// [`None`] for fingers and platforms that don't report it (Bevy doesn't expose the azimuth at all).
+    pub altitude: Option<f32>,
+}
+
+impl EguiPenState {
+    /// Whether the pointer is driven by a stylus (only platforms reporting the altitude can tell it from a finger).
+    pub fn is_pen(&self) -> bool {
+        self.altitude.is_some()
+    }
+
+    /// Reads the pen state of the context, as of the beginning of the current pass.
+    pub fn from_ctx(ctx: &egui::Context) -> Self {
+        ctx.data(|data| data.get_temp(Self::id()).unwrap_or_default())
+    }
+
+    fn id() -> egui::Id {
+        egui::Id::new("bevy_egui_pen_state")
+    }
 }
 
 /// Stores per-context [IME](https://en.wikipedia.org/wiki/Input_method) state used by input and window integration.
@@ -72,15 +127,225 @@ pub struct EguiContextImeState {
     pub ime_rect: Option<egui::Rect>,
 }
 
+/// Tracks windows that [IME](https://en.wikipedia.org/wiki/Input_method) is allowed in.
+///
+/// Shared by [`process_ime_system`] and `picking::process_world_space_ime_system`, so that IME stays allowed
+/// in a window while any context shown in it (either attached to the window or rendered to a world-space mesh) needs it.
+#[derive(Resource, Default, Debug)]
+pub struct EguiWindowImeState {
+    /// Windows that contexts need IME in, indexed by contexts.
+    context_to_window: HashMap<Entity, Entity>,
+    /// Windows that IME is currently allowed in.
+    allowed_windows: HashSet<Entity>,
+}
+
+impl EguiWindowImeState {
+    /// Returns `true` if IME is currently allowed in the window.
+    pub fn is_ime_allowed(&self, window: Entity) -> bool {
+        self.allowed_windows.contains(&window)
+    }
+
+    /// Records the window that the context needs IME in (`None` if it doesn't need IME),
+    /// and allows or disallows IME in the affected windows.
+    pub(crate) fn set_context_window(&mut self, context: Entity, window: Option<Entity>) {
+        let previous_window = match window {
+            Some(window) => self.context_to_window.insert(context, window),
+            None => self.context_to_window.remove(&context),
+        };
+        if previous_window != window {
+            self.update_windows(previous_window.into_iter().chain(window));
+        }
+    }
+
+    /// Forgets contexts that don't match the predicate (e.g. despawned ones), disallowing IME in their windows
+    /// unless other contexts still need it.
+    pub(crate) fn retain_contexts(&mut self, mut predicate: impl FnMut(Entity) -> bool) {
+        let mut windows = Vec::new();
+        self.context_to_window.retain(|&context, &mut window| {
+            let retain = predicate(context);
+            if !retain {
+                windows.push(window);
+            }
+            retain
+        });
+        self.update_windows(windows);
+    }
+
+    fn update_windows(&mut self, windows: impl IntoIterator<Item = Entity>) {
+        bevy_winit::WINIT_WINDOWS.with_borrow(|winit_windows| {
+            for window in windows {
+                let ime_allowed = self.context_to_window.values().any(|&w| w == window);
+                if ime_allowed == self.allowed_windows.contains(&window) {
+                    continue;
+                }
+                if ime_allowed {
+                    self.allowed_windows.insert(window);
+                } else {
+                    self.allowed_windows.remove(&window);
+                }
+                match winit_windows.get_window(window) {
+                    Some(winit_window) => winit_window.set_ime_allowed(ime_allowed),
+                    // A closed window doesn't need to be disallowed.
+                    None if !ime_allowed => {}
+                    None => log::warn!(
+                        "Cannot access an underlying winit window for a window entity {}",
+                        window
+                    ),
+                }
+            }
+        });
+    }
+}
+
 #[derive(Message)]
 /// Wraps Egui messages emitted by [`crate::EguiInputSet`] systems.
 pub struct EguiInputEvent {
     /// Context to pass an message to.
     pub context: Entity,
+    /// System that has written the message.
+    pub source: EguiInputSource,
     /// Wrapped event.
     pub event: egui::Event,
 }
 
+/// The system that has written an [`EguiInputEvent`].
+///
+/// Every variant except [`EguiInputSource::ViewportCommand`] and [`EguiInputSource::Custom`]
+/// corresponds to a system controlled by [`EguiInputSystemSettings`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EguiInputSource {
+    /// [`write_pointer_moved_and_button_messages_system`] (pointer movement).
+    WindowPointerMoved,
+    /// [`write_pointer_moved_and_button_messages_system`] (button presses).
+    PointerButton,
+    /// [`write_window_touch_messages_system`].
+    WindowTouch,
+    /// [`write_non_window_pointer_moved_messages_system`].
+    NonWindowPointerMoved,
+    /// [`write_mouse_wheel_messages_system`].
+    MouseWheel,
+    /// [`write_mouse_motion_messages_system`].
+    MouseMotion,
+    /// [`write_pinch_gesture_messages_system`].
+    PinchGesture,
+    /// [`write_rotation_gesture_messages_system`].
+    RotationGesture,
+    /// [`write_pan_gesture_messages_system`].
+    PanGesture,
+    /// [`write_double_tap_gesture_messages_system`].
+    DoubleTapGesture,
+    /// [`write_non_window_touch_messages_system`].
+    NonWindowTouch,
+    /// [`write_long_press_messages_system`].
+    LongPress,
+    /// [`write_keyboard_input_messages_system`].
+    KeyboardInput,
+    /// [`write_ime_messages_system`].
+    Ime,
+    /// [`write_gamepad_messages_system`].
+    Gamepad,
+    /// [`crate::virtual_cursor::write_gamepad_virtual_cursor_messages_system`].
+    GamepadVirtualCursor,
+    /// `write_picking_pointer_messages_system` (requires the `picking` feature).
+    PickingPointer,
+    /// `write_text_agent_channel_messages_system` (web only).
+    TextAgentChannel,
+    /// `write_web_clipboard_messages_system` (web only, requires the `manage_clipboard` feature).
+    WebClipboard,
+    /// [`crate::output::process_viewport_commands_system`], which turns copy, cut and paste viewport commands into events.
+    ViewportCommand,
+    /// Any other system, e.g. the ones written by users.
+    #[default]
+    Custom,
+}
+
+impl EguiInputSource {
+    /// Returns `true` if the source is a system controlled by [`EguiInputSystemSettings`].
+    pub fn is_input_system(self) -> bool {
+        !matches!(self, Self::ViewportCommand | Self::Custom)
+    }
+
+    /// Returns `true` if the settings enable the system, sources that aren't input systems are always enabled.
+    ///
+    /// Sources that are only available on some platforms or with some features are enabled
+    /// if their settings don't exist.
+    pub fn is_enabled(self, settings: &EguiInputSystemSettings) -> bool {
+        match self {
+            Self::WindowPointerMoved => settings.run_write_window_pointer_moved_messages,
+            Self::PointerButton => settings.run_write_pointer_button_messages,
+            Self::WindowTouch => settings.run_write_window_touch_messages_system,
+            Self::NonWindowPointerMoved => {
+                settings.run_write_non_window_pointer_moved_messages_system
+            }
+            Self::MouseWheel => settings.run_write_mouse_wheel_messages_system,
+            Self::MouseMotion => settings.run_write_mouse_motion_messages_system,
+            Self::PinchGesture => settings.run_write_pinch_gesture_messages_system,
+            Self::RotationGesture => settings.run_write_rotation_gesture_messages_system,
+            Self::PanGesture => settings.run_write_pan_gesture_messages_system,
+            Self::DoubleTapGesture => settings.run_write_double_tap_gesture_messages_system,
+            Self::NonWindowTouch => settings.run_write_non_window_touch_messages_system,
+            Self::LongPress => settings.run_write_long_press_messages_system,
+            Self::KeyboardInput => settings.run_write_keyboard_input_messages_system,
+            Self::Ime => settings.run_write_ime_messages_system,
+            Self::Gamepad => settings.run_write_gamepad_messages_system,
+            Self::GamepadVirtualCursor => settings.run_write_gamepad_virtual_cursor_messages_system,
+            #[cfg(feature = "picking")]
+            Self::PickingPointer => settings.run_write_picking_pointer_messages_system,
+            #[cfg(target_arch = "wasm32")]
+            Self::TextAgentChannel => settings.run_write_text_agent_channel_messages_system,
+            #[cfg(all(feature = "manage_clipboard", target_arch = "wasm32"))]
+            Self::WebClipboard => settings.run_write_web_clipboard_messages_system,
+            _ => true,
+        }
+    }
+}
+
+/// Inspects, modifies, drops or injects Egui events of a context right before [`write_egui_input_system`]
+/// pushes them into [`EguiInput`], see [`EguiInputFilters`].
+///
+/// Closures taking `&mut Vec<egui::Event>` implement this trait.
+pub trait EguiInputFilter: Send + Sync + 'static {
+    /// Filters the events of the context received during the current frame,
+    /// including the [`egui::Event::ModifiersChanged`] event that's added every frame.
+    fn filter(&mut self, events: &mut Vec<egui::Event>);
+}
+
+impl<F> EguiInputFilter for F
+where
+    F: FnMut(&mut Vec<egui::Event>) + Send + Sync + 'static,
+{
+    fn filter(&mut self, events: &mut Vec<egui::Event>) {
+        self(events)
+    }
+}
+
+/// Add this component to an [`crate::EguiContext`] entity to filter its input, the filters are applied in order.
+///
+/// ```
+/// use bevy_egui::input::EguiInputFilters;
+///
+/// // Disable Tab focus cycling.
+/// let filters = EguiInputFilters::default().with(|events: &mut Vec<egui::Event>| {
+///     events.retain(|event| !matches!(event, egui::Event::Key { key: egui::Key::Tab, .. }));
+/// });
+/// ```
+#[derive(Component, Default)]
+pub struct EguiInputFilters(pub Vec<Box<dyn EguiInputFilter>>);
+
+impl EguiInputFilters {
+    /// Appends a filter.
+    pub fn with(mut self, filter: impl EguiInputFilter) -> Self {
+        self.push(filter);
+        self
+    }
+
+    /// Appends a filter.
+    pub fn push(&mut self, filter: impl EguiInputFilter) {
+        self.0.push(Box::new(filter));
+    }
+}
+
 #[derive(Message)]
 /// Wraps [`bevy::FileDragAndDrop`](bevy_window::FileDragAndDrop) messages emitted by [`crate::EguiInputSet`] systems.
 pub struct EguiFileDragAndDropMessage {
@@ -90,6 +355,17 @@ pub struct EguiFileDragAndDropMessage {
     pub message: FileDragAndDrop,
 }
 
+#[derive(Message, Clone, Debug)]
+/// Is emitted when an image is pasted from the clipboard, as Egui doesn't have an event for pasting images.
+///
+/// Images are read with [`crate::EguiClipboard::get_image`] if the clipboard doesn't contain any text.
+pub struct EguiClipboardImagePasted {
+    /// Context that the image was pasted to.
+    pub context: Entity,
+    /// Pasted image.
+    pub image: egui::ColorImage,
+}
+
 #[derive(Resource, Clone)]
 /// Insert this resource when a pointer hovers over a non-window (e.g. world-space) [`EguiContext`] entity.
 /// Also, make sure to update an [`EguiContextPointerPosition`] component of a hovered entity.
@@ -114,7 +390,27 @@ pub struct HoveredNonWindowEguiContext(pub Entity);
 #[derive(Resource, Clone)]
 pub struct FocusedNonWindowEguiContext(pub Entity);
 
+/// An entity event triggered for a context when it gains keyboard focus, i.e. when [`egui::RawInput::focused`] becomes `true`.
+///
+/// A context is focused if it's the [`FocusedNonWindowEguiContext`], or if its window is focused
+/// and there's no focused non-window context. See also [`crate::BevyEguiCommandsExt::focus_egui_context`].
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EguiContextFocused {
+    /// The focused context.
+    pub entity: Entity,
+}
+
+/// An entity event triggered for a context when it loses keyboard focus (see [`EguiContextFocused`]).
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EguiContextUnfocused {
+    /// The unfocused context.
+    pub entity: Entity,
+}
+
 /// Stores "pressed" state of modifier keys.
+///
+/// As a resource, it holds the state of the window that has received keyboard input or focus last,
+/// which is used for contexts that aren't attached to a window. See [`WindowModifierKeysState`] for the state of each window.
 #[derive(Resource, Clone, Copy, Debug)]
 pub struct ModifierKeysState {
     /// Indicates whether the [`Key::Shift`] key is pressed.
@@ -125,6 +421,8 @@ pub struct ModifierKeysState {
     pub alt: bool,
     /// Indicates whether the [`Key::Super`] (or [`Key::Meta`]) key is pressed.
     pub win: bool,
+    /// Indicates whether the [`Key::AltGraph`] key is pressed.
+    pub alt_gr: bool,
     is_macos: bool,
 }
 
@@ -135,6 +433,7 @@ impl Default for ModifierKeysState {
             ctrl: false,
             alt: false,
             win: false,
+            alt_gr: false,
             is_macos: false,
         };
 
@@ -169,8 +468,20 @@ impl ModifierKeysState {
 
     /// Returns `true` if modifiers shouldn't prmessage text input (we don't want to put characters on pressing Ctrl+A, etc).
     pub fn text_input_is_allowed(&self) -> bool {
-        // Ctrl + Alt enables AltGr which is used to print special characters.
-        !self.win && !self.ctrl || !self.is_macos && self.ctrl && self.alt
+        !self.win && !self.ctrl
+    }
+
+    /// Returns `true` if `text` was typed with AltGr, which is used to type special characters on many keyboard layouts.
+    ///
+    /// Besides [`Key::AltGraph`], Windows reports AltGr as Ctrl + Alt, so there the combination counts as AltGr
+    /// if it produced a character other than the one of the unmodified key (e.g. "ż" for [`KeyCode::KeyZ`] on Polish layouts).
+    pub fn is_alt_gr(&self, text: &str, key_code: KeyCode) -> bool {
+        self.alt_gr
+            || cfg!(target_os = "windows")
+                && self.ctrl
+                && self.alt
+                && crate::helpers::bevy_to_egui_physical_key(&key_code)
+                    .is_none_or(|key| !text.eq_ignore_ascii_case(key.symbol_or_name()))
     }
 
     fn reset(&mut self) {
@@ -178,6 +489,27 @@ impl ModifierKeysState {
         self.ctrl = false;
         self.alt = false;
         self.win = false;
+        self.alt_gr = false;
+    }
+}
+
+/// Stores "pressed" state of modifier keys for each window, as holding a key in one window
+/// shouldn't affect the contexts of other windows.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct WindowModifierKeysState {
+    windows: HashMap<Entity, ModifierKeysState>,
+    released: ModifierKeysState,
+}
+
+impl WindowModifierKeysState {
+    /// Returns the state of a window (all keys are released if the window hasn't received any keyboard input).
+    pub fn get(&self, window: Entity) -> &ModifierKeysState {
+        self.windows.get(&window).unwrap_or(&self.released)
+    }
+
+    /// Returns the mutable state of a window.
+    pub fn get_mut(&mut self, window: Entity) -> &mut ModifierKeysState {
+        self.windows.entry(window).or_insert(self.released)
     }
 }
 
@@ -202,6 +534,7 @@ impl WindowToEguiContextMap {
         >,
         primary_window: Query<Entity, With<bevy_window::PrimaryWindow>>,
         event_loop_proxy: Option<Res<bevy_winit::EventLoopProxyWrapper>>,
+        repaint_scheduler: Res<EguiRepaintScheduler>,
     ) {
         for (egui_context_entity, render_target, mut egui_context) in added_contexts {
             if let bevy_camera::RenderTarget::Window(window_ref) = render_target
@@ -216,17 +549,18 @@ impl WindowToEguiContextMap {
 
                 // The resource doesn't exist in the headless mode
                 // or if the EventLoopProxy uses another event.
-                let Some(event_loop_proxy) = &event_loop_proxy else {
-                    continue;
-                };
-
-                let message_loop_proxy = (*event_loop_proxy).clone();
+                let message_loop_proxy = event_loop_proxy
+                    .as_ref()
+                    .map(|event_loop_proxy| (**event_loop_proxy).clone());
+                let repaint_requests = repaint_scheduler.requests();
                 egui_context
                     .get_mut()
                     .set_request_repaint_callback(move |repaint_info| {
-                        // TODO: find a lightweight async timer implementation that also works in WASM
-                        //  to support non-zero wake-ups as well.
-                        if repaint_info.delay.is_zero() {
+                        if !repaint_info.delay.is_zero() {
+                            // Delayed repaints are handled by `schedule_repaints_system`.
+                            repaint_requests
+                                .request_repaint_after(egui_context_entity, repaint_info.delay);
+                        } else if let Some(message_loop_proxy) = &message_loop_proxy {
                             log::trace!("Sending the WakeUp message");
                             let _ = message_loop_proxy.send_event(WinitUserEvent::WakeUp);
                         }
@@ -372,37 +706,59 @@ impl<'w, 's, M: Message> EguiContextMessageReader<'w, 's, M> {
     }
 }
 
-/// Reads [`KeyboardInput`] messages to update the [`ModifierKeysState`] resource.
+/// Reads [`KeyboardInput`] messages to update the [`WindowModifierKeysState`] and [`ModifierKeysState`] resources.
+///
+/// Modifiers of a window are cleared when it loses focus, and all of them are cleared on [`KeyboardFocusLost`].
 pub fn write_modifiers_keys_state_system(
     mut keyboard_input_reader: MessageReader<KeyboardInput>,
     mut focus_reader: MessageReader<KeyboardFocusLost>,
+    mut window_focused_reader: MessageReader<WindowFocused>,
     mut modifier_keys_state: ResMut<ModifierKeysState>,
+    mut window_modifier_keys_state: ResMut<WindowModifierKeysState>,
 ) {
-    // If window focus is lost, clear all modifiers to avoid stuck keys.
+    for WindowFocused { window, focused } in window_focused_reader.read() {
+        let window_state = window_modifier_keys_state.get_mut(*window);
+        if *focused {
+            *modifier_keys_state = *window_state;
+        } else {
+            window_state.reset();
+        }
+    }
+
+    // If the app focus is lost, clear all modifiers to avoid stuck keys.
     if !focus_reader.is_empty() {
         focus_reader.clear();
         modifier_keys_state.reset();
+        window_modifier_keys_state.windows.clear();
     }
 
     for message in keyboard_input_reader.read() {
         let KeyboardInput {
-            logical_key, state, ..
+            logical_key,
+            state,
+            window,
+            ..
         } = message;
+        let window_state = window_modifier_keys_state.get_mut(*window);
         match logical_key {
             Key::Shift => {
-                modifier_keys_state.shift = state.is_pressed();
+                window_state.shift = state.is_pressed();
             }
             Key::Control => {
-                modifier_keys_state.ctrl = state.is_pressed();
+                window_state.ctrl = state.is_pressed();
             }
             Key::Alt => {
-                modifier_keys_state.alt = state.is_pressed();
+                window_state.alt = state.is_pressed();
             }
             Key::Super | Key::Meta => {
-                modifier_keys_state.win = state.is_pressed();
+                window_state.win = state.is_pressed();
+            }
+            Key::AltGraph => {
+                window_state.alt_gr = state.is_pressed();
             }
             _ => {}
         };
+        *modifier_keys_state = *window_state;
     }
 }
 
@@ -418,6 +774,114 @@ pub fn read_egui_zoom_factor_system(
     }
 }
 
+/// Writes [`egui::ViewportInfo`] of the viewport a context renders to, based on the [`Window`]
+/// and [`Monitor`] components.
+///
+/// The info gets updated only when the window, its monitor or [`EguiZoomFactor`] change.
+/// As Bevy doesn't track whether a window is minimized or maximized, these values are read
+/// from the underlying winit window (if there's any).
+#[allow(clippy::type_complexity)]
+pub fn write_viewport_info_system(
+    mut egui_contexts: Query<(
+        Entity,
+        &mut EguiInput,
+        Ref<EguiContextSettings>,
+        Ref<EguiZoomFactor>,
+        Ref<EguiContext>,
+    )>,
+    windows: Query<(Ref<Window>, Option<Ref<OnMonitor>>)>,
+    monitors: Query<Ref<Monitor>>,
+    mut window_occluded_reader: MessageReader<WindowOccluded>,
+    window_to_egui_context_map: Res<WindowToEguiContextMap>,
+    _non_send_marker: NonSendMarker,
+) {
+    let occluded_windows: HashMap<Entity, bool> = window_occluded_reader
+        .read()
+        .map(|message| (message.window, message.occluded))
+        .collect();
+
+    for (entity, mut egui_input, settings, zoom_factor, egui_context) in &mut egui_contexts {
+        if !settings
+            .input_system_settings
+            .run_write_viewport_info_system
+        {
+            continue;
+        }
+
+        let Some(&window_entity) = window_to_egui_context_map.context_to_window.get(&entity) else {
+            continue;
+        };
+        let Ok((window, on_monitor)) = windows.get(window_entity) else {
+            continue;
+        };
+        let monitor = on_monitor
+            .as_ref()
+            .and_then(|on_monitor| monitors.get(on_monitor.0).ok());
+        let occluded = occluded_windows.get(&window_entity).copied();
+
+        let is_changed = egui_context.is_added()
+            || settings.is_changed()
+            || zoom_factor.is_changed()
+            || window.is_changed()
+            || occluded.is_some()
+            || on_monitor.is_some_and(|on_monitor| on_monitor.is_changed())
+            || monitor.as_ref().is_some_and(|monitor| monitor.is_changed());
+        if !is_changed {
+            continue;
+        }
+
+        let viewport_id = egui_input.viewport_id;
+        let viewport_info = egui_input.viewports.entry(viewport_id).or_default();
+        update_viewport_info(
+            viewport_info,
+            &window,
+            monitor.as_deref(),
+            zoom_factor.zoom_factor,
+        );
+        if occluded.is_some() {
+            viewport_info.occluded = occluded;
+        }
+
+        bevy_winit::WINIT_WINDOWS.with_borrow(|winit_windows| {
+            if let Some(winit_window) = winit_windows.get_window(window_entity) {
+                viewport_info.minimized = winit_window.is_minimized();
+                viewport_info.maximized = Some(winit_window.is_maximized());
+            }
+        });
+    }
+}
+
+fn update_viewport_info(
+    viewport_info: &mut egui::ViewportInfo,
+    window: &Window,
+    monitor: Option<&Monitor>,
+    zoom_factor: f32,
+) {
+    viewport_info.title = Some(window.title.clone());
+    viewport_info.focused = Some(window.focused);
+    viewport_info.fullscreen = Some(!matches!(window.mode, WindowMode::Windowed));
+
+    // Bevy doesn't expose the size of window decorations,
+    // so both the rects are calculated from the window's outer position and its inner size.
+    let size = egui::vec2(window.width(), window.height()) / zoom_factor;
+    let rect = match window.position {
+        WindowPosition::At(position) => Some(egui::Rect::from_min_size(
+            vec2_into_egui_pos2(position.as_vec2() / (window.scale_factor() * zoom_factor)),
+            size,
+        )),
+        WindowPosition::Automatic | WindowPosition::Centered(_) => None,
+    };
+    viewport_info.inner_rect = rect;
+    viewport_info.outer_rect = rect;
+
+    viewport_info.monitor_size = monitor.map(|monitor| {
+        egui::vec2(
+            monitor.physical_width as f32,
+            monitor.physical_height as f32,
+        ) / (monitor.scale_factor as f32 * zoom_factor)
+    });
+}
+
 /// Reads [`WindowEvent`] messages and wraps cursor movement and mouse button input into [`EguiInputEvent`]s in the order that the operating system delivered them.
 ///
 /// Mouse button messages do not contain a pointer position, so preserving their ordering with cursor movement messages is necessary to associate them with the correct position. Button messages can be redirected to [`HoveredNonWindowEguiContext`].
@@ -427,7 +891,7 @@ pub fn read_egui_zoom_factor_system(
 pub fn write_pointer_moved_and_button_messages_system(
     egui_global_settings: Res<EguiGlobalSettings>,
     mut commands: Commands,
-    modifier_keys_state: Res<ModifierKeysState>,
+    window_modifier_keys_state: Res<WindowModifierKeysState>,
     mut window_event_reader: MessageReader<WindowEvent>,
     mut egui_input_message_writer: MessageWriter<EguiInputEvent>,
     mut egui_contexts: Query<
@@ -441,8 +905,6 @@ pub fn write_pointer_moved_and_button_messages_system(
     window_to_egui_context_map: Res<WindowToEguiContextMap>,
     hovered_non_window_egui_context: Option<Res<HoveredNonWindowEguiContext>>,
 ) {
-    let modifiers = modifier_keys_state.to_egui_modifiers();
-
     for window_event in window_event_reader.read() {
         match window_event {
             WindowEvent::CursorMoved(message) => {
@@ -479,6 +941,7 @@ pub fn write_pointer_moved_and_button_messages_system(
                     pointer_position.position = position;
                     egui_input_message_writer.write(EguiInputEvent {
                         context,
+                        source: EguiInputSource::WindowPointerMoved,
                         event: egui::Event::PointerMoved(position),
                     });
                 }
@@ -503,6 +966,9 @@ pub fn write_pointer_moved_and_button_messages_system(
                     continue;
                 };
                 let pressed = message.state == ButtonState::Pressed;
+                let modifiers = window_modifier_keys_state
+                    .get(message.window)
+                    .to_egui_modifiers();
 
                 let contexts: Vec<_> =
                     if let Some(hovered_context) = &hovered_non_window_egui_context {
@@ -533,6 +999,7 @@ pub fn write_pointer_moved_and_button_messages_system(
 
                     egui_input_message_writer.write(EguiInputEvent {
                         context,
+                        source: EguiInputSource::PointerButton,
                         event: egui::Event::PointerButton {
                             pos: pointer_position.position,
                             button,
@@ -590,21 +1057,24 @@ pub fn write_non_window_pointer_moved_messages_system(
 
     egui_input_message_writer.write(EguiInputEvent {
         context: *hovered_non_window_egui_context,
+        source: EguiInputSource::NonWindowPointerMoved,
         event: egui::Event::PointerMoved(context_pointer_position.position),
     });
 }
 
 /// Reads [`MouseWheel`] messages and wraps them into [`EguiInputEvent`], can redirect messages to [`HoveredNonWindowEguiContext`].
 pub fn write_mouse_wheel_messages_system(
-    modifier_keys_state: Res<ModifierKeysState>,
+    window_modifier_keys_state: Res<WindowModifierKeysState>,
     mut mouse_wheel_reader: EguiContextMessageReader<MouseWheel>,
     mut egui_input_message_writer: MessageWriter<EguiInputEvent>,
     egui_contexts: Query<&EguiContextSettings, With<EguiContext>>,
 ) {
-    let modifiers = modifier_keys_state.to_egui_modifiers();
     for (message, context) in
         mouse_wheel_reader.read_with_non_window_hovered(|message| message.window)
     {
+        let modifiers = window_modifier_keys_state
+            .get(message.window)
+            .to_egui_modifiers();
         let delta = egui::vec2(message.x, message.y);
         let unit = match message.unit {
             MouseScrollUnit::Line => egui::MouseWheelUnit::Line,
@@ -624,6 +1094,7 @@ pub fn write_mouse_wheel_messages_system(
 
         egui_input_message_writer.write(EguiInputEvent {
             context,
+            source: EguiInputSource::MouseWheel,
             event: egui::Event::MouseWheel {
                 unit,
                 delta,
@@ -634,6 +1105,64 @@ pub fn write_mouse_wheel_messages_system(
     }
 }
 
+/// Reads [`MouseMotion`] messages and wraps them into [`EguiInputEvent`] with [`egui::Event::MouseMoved`].
+///
+/// Unlike [`CursorMoved`], raw mouse motion is reported even when the cursor is locked
+/// (see [`bevy_window::CursorGrabMode`]), which is useful for widgets like drag values or orbit controls.
+/// Bevy's `MouseMotion` doesn't carry a window entity, so this sends events to the hovered non-window context
+/// if one exists, or to the contexts of focused windows.
+pub fn write_mouse_motion_messages_system(
+    mut mouse_motion_reader: MessageReader<MouseMotion>,
+    hovered_non_window_egui_context: Option<Res<HoveredNonWindowEguiContext>>,
+    windows: Query<(Entity, &Window)>,
+    map: Res<WindowToEguiContextMap>,
+    egui_contexts: Query<&EguiContextSettings, With<EguiContext>>,
+    mut egui_input_message_writer: MessageWriter<EguiInputEvent>,
+) {
+    let delta: bevy_math::Vec2 = mouse_motion_reader
+        .read()
+        .map(|message| message.delta)
+        .sum();
+    if delta == bevy_math::Vec2::ZERO {
+        return;
+    }
+
+    let contexts: Vec<Entity> = match hovered_non_window_egui_context {
+        Some(context) => vec![context.0],
+        None => focused_window_contexts(&windows, &map).collect(),
+    };
+    for context in contexts {
+        let Ok(context_settings) = egui_contexts.get(context) else {
+            continue;
+        };
+        if !context_settings
+            .input_system_settings
+            .run_write_mouse_motion_messages_system
+        {
+            continue;
+        }
+
+        egui_input_message_writer.write(EguiInputEvent {
+            context,
+            source: EguiInputSource::MouseMotion,
+            event: egui::Event::MouseMoved(egui::vec2(delta.x, delta.y)),
+        });
+    }
+}
+
+/// Returns contexts of all focused windows.
+fn focused_window_contexts<'a>(
+    windows: &'a Query<(Entity, &Window)>,
+    map: &'a WindowToEguiContextMap,
+) -> impl Iterator<Item = Entity> + 'a {
+    windows
+        .iter()
+        .filter(|(_, window)| window.focused)
+        .filter_map(|(window, _)| map.window_to_contexts.get(&window))
+        .flatten()
+        .copied()
+}
+
 /// Reads [`PinchGesture`] messages and wraps them into [`EguiInputEvent`] with [`egui::Event::Zoom`].
 ///
 /// Bevy's `PinchGesture` doesn't carry a window entity, so this broadcasts to all window contexts
@@ -648,46 +1177,176 @@ pub fn write_pinch_gesture_messages_system(
         // Match egui-winit: positive delta = magnification, negative = shrink
         let zoom_factor = message.0.exp();
 
-        if let Some(HoveredNonWindowEguiContext(context)) =
-            hovered_non_window_egui_context.as_deref()
-        {
+        for context in gesture_contexts(hovered_non_window_egui_context.as_deref(), &map) {
             egui_input_message_writer.write(EguiInputEvent {
-                context: *context,
+                context,
+                source: EguiInputSource::PinchGesture,
                 event: egui::Event::Zoom(zoom_factor),
             });
-        } else {
-            for contexts in map.window_to_contexts.values() {
-                for &context in contexts {
-                    egui_input_message_writer.write(EguiInputEvent {
-                        context,
-                        event: egui::Event::Zoom(zoom_factor),
-                    });
-                }
+        }
+    }
+}
+
+/// Reads [`RotationGesture`] messages and wraps them into [`EguiInputEvent`] with [`egui::Event::Rotate`],
+/// which Egui exposes as [`egui::InputState::rotation_delta`].
+///
+/// Trackpad rotation isn't reported in [`egui::MultiTouchInfo`], as Egui builds it only from touch events,
+/// and [`RotationGesture`] has no touch positions or phases to synthesize them from.
+///
+/// Touchscreen rotation doesn't go through this system: Egui builds [`egui::MultiTouchInfo`]
+/// from the touch events sent by [`write_window_touch_messages_system`].
+/// Like [`write_pinch_gesture_messages_system`], this broadcasts to all window contexts
+/// (or the hovered non-window context if one exists).
+pub fn write_rotation_gesture_messages_system(
+    mut rotation_reader: MessageReader<RotationGesture>,
+    hovered_non_window_egui_context: Option<Res<HoveredNonWindowEguiContext>>,
+    mut egui_input_message_writer: MessageWriter<EguiInputEvent>,
+    map: Res<WindowToEguiContextMap>,
+) {
+    for message in rotation_reader.read() {
+        // Bevy reports counterclockwise rotation in degrees, Egui expects clockwise rotation in radians.
+        let radians = -message.0.to_radians();
+
+        for context in gesture_contexts(hovered_non_window_egui_context.as_deref(), &map) {
+            egui_input_message_writer.write(EguiInputEvent {
+                context,
+                source: EguiInputSource::RotationGesture,
+                event: egui::Event::Rotate(radians),
+            });
+        }
+    }
+}
+
+/// Reads [`PanGesture`] messages and wraps them into [`EguiInputEvent`] with [`egui::Event::MouseWheel`]
+/// in points, so that trackpad panning scrolls smoothly.
+///
+/// Like [`write_pinch_gesture_messages_system`], this broadcasts to all window contexts
+/// (or the hovered non-window context if one exists).
+pub fn write_pan_gesture_messages_system(
+    modifier_keys_state: Res<ModifierKeysState>,
+    window_modifier_keys_state: Res<WindowModifierKeysState>,
+    mut pan_reader: MessageReader<PanGesture>,
+    hovered_non_window_egui_context: Option<Res<HoveredNonWindowEguiContext>>,
+    mut egui_input_message_writer: MessageWriter<EguiInputEvent>,
+    map: Res<WindowToEguiContextMap>,
+) {
+    for message in pan_reader.read() {
+        for context in gesture_contexts(hovered_non_window_egui_context.as_deref(), &map) {
+            let modifiers = context_modifiers(
+                context,
+                &map,
+                &window_modifier_keys_state,
+                &modifier_keys_state,
+            );
+            egui_input_message_writer.write(EguiInputEvent {
+                context,
+                source: EguiInputSource::PanGesture,
+                event: egui::Event::MouseWheel {
+                    unit: egui::MouseWheelUnit::Point,
+                    delta: egui::vec2(message.0.x, message.0.y),
+                    phase: TouchPhase::Move,
+                    modifiers,
+                },
+            });
+        }
+    }
+}
+
+/// Reads [`DoubleTapGesture`] messages and wraps them into [`EguiInputEvent`]
+/// with a primary button click at the pointer position.
+///
+/// Only the second click of the double tap is emitted, so that it doesn't click a widget twice.
+/// Egui recognizes a double click if the first tap has been delivered as a click.
+///
+/// Like [`write_pinch_gesture_messages_system`], this broadcasts to all window contexts
+/// (or the hovered non-window context if one exists).
+pub fn write_double_tap_gesture_messages_system(
+    modifier_keys_state: Res<ModifierKeysState>,
+    window_modifier_keys_state: Res<WindowModifierKeysState>,
+    mut double_tap_reader: MessageReader<DoubleTapGesture>,
+    hovered_non_window_egui_context: Option<Res<HoveredNonWindowEguiContext>>,
+    mut egui_input_message_writer: MessageWriter<EguiInputEvent>,
+    map: Res<WindowToEguiContextMap>,
+    egui_contexts: Query<&EguiContextPointerPosition, With<EguiContext>>,
+) {
+    for _ in double_tap_reader.read() {
+        for context in gesture_contexts(hovered_non_window_egui_context.as_deref(), &map) {
+            let Ok(pointer_position) = egui_contexts.get(context) else {
+                continue;
+            };
+            let modifiers = context_modifiers(
+                context,
+                &map,
+                &window_modifier_keys_state,
+                &modifier_keys_state,
+            );
+            for pressed in [true, false] {
+                egui_input_message_writer.write(EguiInputEvent {
+                    context,
+                    source: EguiInputSource::DoubleTapGesture,
+                    event: egui::Event::PointerButton {
+                        pos: pointer_position.position,
+                        button: egui::PointerButton::Primary,
+                        pressed,
+                        modifiers,
+                    },
+                });
             }
         }
     }
 }
 
+/// Returns modifiers of the context's window, or of the [`ModifierKeysState`] resource for contexts without a window.
+pub(crate) fn context_modifiers(
+    context: Entity,
+    map: &WindowToEguiContextMap,
+    window_modifier_keys_state: &WindowModifierKeysState,
+    modifier_keys_state: &ModifierKeysState,
+) -> egui::Modifiers {
+    match map.context_to_window.get(&context) {
+        Some(window) => window_modifier_keys_state.get(*window).to_egui_modifiers(),
+        None => modifier_keys_state.to_egui_modifiers(),
+    }
+}
+
+/// Returns the hovered non-window context if one exists, or contexts of all windows.
+fn gesture_contexts(
+    hovered_non_window_egui_context: Option<&HoveredNonWindowEguiContext>,
+    map: &WindowToEguiContextMap,
+) -> Vec<Entity> {
+    match hovered_non_window_egui_context {
+        Some(HoveredNonWindowEguiContext(context)) => vec![*context],
+        None => map.window_to_contexts.values().flatten().copied().collect(),
+    }
+}
+
 /// Reads [`KeyboardInput`] messages and wraps them into [`EguiInputEvent`], can redirect messages to [`FocusedNonWindowEguiContext`].
+///
+/// Pasting an image writes a single [`EguiClipboardImagePasted`] message: for the [`FocusedNonWindowEguiContext`],
+/// the only context of the window, or the context of the window that wants keyboard input (see [`EguiContextWantsInput`]).
 pub fn write_keyboard_input_messages_system(
-    modifier_keys_state: Res<ModifierKeysState>,
-    #[cfg(all(
-        feature = "manage_clipboard",
-        not(target_os = "android"),
-        not(target_arch = "wasm32")
-    ))]
+    window_modifier_keys_state: Res<WindowModifierKeysState>,
+    #[cfg(not(all(feature = "manage_clipboard", target_arch = "wasm32")))]
     mut egui_clipboard: ResMut<crate::EguiClipboard>,
+    #[cfg(not(all(feature = "manage_clipboard", target_arch = "wasm32")))]
+    mut image_pasted_writer: MessageWriter<EguiClipboardImagePasted>,
     mut keyboard_input_reader: EguiContextMessageReader<KeyboardInput>,
     mut egui_input_message_writer: MessageWriter<EguiInputEvent>,
-    egui_contexts: Query<&EguiContextSettings, With<EguiContext>>,
+    egui_contexts: Query<(&EguiContextSettings, &EguiContextWantsInput), With<EguiContext>>,
 ) {
-    let modifiers = modifier_keys_state.to_egui_modifiers();
+    // Contexts that an image can be pasted to, grouped by windows.
+    #[cfg(not(all(feature = "manage_clipboard", target_arch = "wasm32")))]
+    let mut image_paste_contexts: Vec<(Entity, Vec<Entity>)> = Vec::new();
+
     for (message, context) in
         keyboard_input_reader.read_with_non_window_focused(|message| message.window)
     {
-        let Ok(context_settings) = egui_contexts.get(context) else {
+        let Ok((context_settings, _)) = egui_contexts.get(context) else {
             continue;
         };
+        // Contexts that aren't attached to a window still get modifiers of the window the input comes from.
+        let modifier_keys_state = window_modifier_keys_state.get(message.window);
+        let modifiers = modifier_keys_state.to_egui_modifiers();
 
         if !context_settings
             .input_system_settings
@@ -696,22 +1355,33 @@ pub fn write_keyboard_input_messages_system(
             continue;
         }
 
-        if modifier_keys_state.text_input_is_allowed() && message.state.is_pressed() {
-            match &message.logical_key {
-                Key::Character(char) if char.matches(char::is_control).count() == 0 => {
-                    egui_input_message_writer.write(EguiInputEvent {
-                        context,
-                        event: egui::Event::Text(char.to_string()),
-                    });
-                }
-                Key::Space => {
-                    egui_input_message_writer.write(EguiInputEvent {
-                        context,
-                        event: egui::Event::Text(" ".to_string()),
-                    });
-                }
-                _ => (),
+        let text = match &message.logical_key {
+            Key::Character(char) if char.matches(char::is_control).count() == 0 => {
+                Some(char.to_string())
+            }
+            Key::Space => Some(" ".to_string()),
+            _ => None,
+        }
+        .filter(|_| message.state.is_pressed());
+        let is_alt_gr = text
+            .as_ref()
+            .is_some_and(|text| modifier_keys_state.is_alt_gr(text, message.key_code));
+        let text = text.filter(|_| modifier_keys_state.text_input_is_allowed() || is_alt_gr);
+        // Characters typed with AltGr shouldn't trigger shortcuts, such as Ctrl+Z (AltGr+Z types "ż" on Polish layouts).
+        let modifiers = if is_alt_gr {
+            egui::Modifiers {
+                shift: modifiers.shift,
+                ..egui::Modifiers::NONE
             }
+        } else {
+            modifiers
+        };
+        if let Some(text) = text {
+            egui_input_message_writer.write(EguiInputEvent {
+                context,
+                source: EguiInputSource::KeyboardInput,
+                event: egui::Event::Text(text),
+            });
         }
 
         let key = crate::helpers::bevy_to_egui_key(&message.logical_key);
@@ -726,61 +1396,280 @@ pub fn write_keyboard_input_messages_system(
         let egui_message = egui::Event::Key {
             key,
             pressed: message.state.is_pressed(),
-            repeat: false,
+            repeat: message.repeat,
             modifiers,
             physical_key,
         };
         egui_input_message_writer.write(EguiInputEvent {
             context,
+            source: EguiInputSource::KeyboardInput,
             event: egui_message,
         });
 
         // We also check that it's a `ButtonState::Pressed` message, as we don't want to
         // copy, cut or paste on the key release.
-        #[cfg(all(
-            feature = "manage_clipboard",
-            not(target_os = "android"),
-            not(target_arch = "wasm32")
-        ))]
+        // On web, the `copy`/`cut`/`paste` events are received from the browser instead.
+        #[cfg(not(all(feature = "manage_clipboard", target_arch = "wasm32")))]
         if modifiers.command && message.state.is_pressed() {
             match key {
                 egui::Key::C => {
                     egui_input_message_writer.write(EguiInputEvent {
                         context,
+                        source: EguiInputSource::KeyboardInput,
                         event: egui::Event::Copy,
                     });
                 }
                 egui::Key::X => {
                     egui_input_message_writer.write(EguiInputEvent {
                         context,
+                        source: EguiInputSource::KeyboardInput,
                         event: egui::Event::Cut,
                     });
                 }
-                egui::Key::V => {
-                    if let Some(contents) = egui_clipboard.get_text() {
+                egui::Key::V => match egui_clipboard.get_text() {
+                    Some(contents) if !contents.is_empty() => {
                         egui_input_message_writer.write(EguiInputEvent {
                             context,
+                            source: EguiInputSource::KeyboardInput,
                             event: egui::Event::Text(contents),
                         });
                     }
-                }
+                    _ => match image_paste_contexts
+                        .iter_mut()
+                        .find(|(window, _)| *window == message.window)
+                    {
+                        Some((_, contexts)) => contexts.push(context),
+                        None => image_paste_contexts.push((message.window, vec![context])),
+                    },
+                },
                 _ => {}
             }
         }
     }
+
+    #[cfg(not(all(feature = "manage_clipboard", target_arch = "wasm32")))]
+    for (_, contexts) in image_paste_contexts {
+        let focused_context = match contexts[..] {
+            [context] => Some(context),
+            _ => contexts.into_iter().find(|context| {
+                egui_contexts
+                    .get(*context)
+                    .is_ok_and(|(_, wants_input)| wants_input.wants_keyboard_input())
+            }),
+        };
+        if let Some(context) = focused_context
+            && let Some(image) = egui_clipboard.get_image()
+        {
+            image_pasted_writer.write(EguiClipboardImagePasted { context, image });
+        }
+    }
 }
 
-/// Reads [`Ime`] messages and wraps them into [`EguiInputEvent`], can redirect messages to [`FocusedNonWindowEguiContext`].
-pub fn write_ime_messages_system(
-    mut ime_reader: EguiContextMessageReader<Ime>,
-    mut egui_input_message_writer: MessageWriter<EguiInputEvent>,
-    mut egui_contexts: Query<
-        (
-            Entity,
-            &EguiContextSettings,
-            &mut EguiContextImeState,
-            &EguiOutput,
-        ),
+/// A key (with modifiers) that a gamepad button is mapped to, see [`EguiGamepadMapping`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EguiGamepadKey {
+    /// Egui key.
+    pub key: egui::Key,
+    /// Modifiers that are sent with the key.
+    pub modifiers: Modifiers,
+}
+
+impl EguiGamepadKey {
+    /// Creates a mapping to a key without modifiers.
+    pub fn new(key: egui::Key) -> Self {
+        Self {
+            key,
+            modifiers: Modifiers::NONE,
+        }
+    }
+
+    /// Creates a mapping to a key with modifiers.
+    pub fn with_modifiers(key: egui::Key, modifiers: Modifiers) -> Self {
+        Self { key, modifiers }
+    }
+}
+
+/// Configures how [`write_gamepad_messages_system`] translates gamepad input into Egui events.
+///
+/// By default, D-pad and the left stick move focus, South (Xbox: A) confirms, East (Xbox: B) cancels,
+/// shoulder buttons move focus to the next or previous widget, and the right stick scrolls.
+#[derive(Resource, Clone, Debug)]
+pub struct EguiGamepadMapping {
+    /// Keys that are sent when the gamepad buttons are pressed or released.
+    pub buttons: HashMap<GamepadButton, EguiGamepadKey>,
+    /// Whether the left stick sends arrow keys, similar to D-pad.
+    pub left_stick_navigation: bool,
+    /// The left stick deflection (from `0.0` to `1.0`) after which it's considered pressed in a direction.
+    pub left_stick_threshold: f32,
+    /// Scrolling speed of the right stick in points per second, set to `0.0` to disable scrolling.
+    ///
+    /// Scrolling is sent as [`egui::Event::MouseWheel`], so it affects the area under the pointer.
+    pub right_stick_scroll_speed: f32,
+    /// The right stick deflection (from `0.0` to `1.0`) below which it's ignored.
+    pub right_stick_dead_zone: f32,
+}
+
+impl Default for EguiGamepadMapping {
+    fn default() -> Self {
+        Self {
+            buttons: HashMap::from_iter([
+                (
+                    GamepadButton::DPadUp,
+                    EguiGamepadKey::new(egui::Key::ArrowUp),
+                ),
+                (
+                    GamepadButton::DPadDown,
+                    EguiGamepadKey::new(egui::Key::ArrowDown),
+                ),
+                (
+                    GamepadButton::DPadLeft,
+                    EguiGamepadKey::new(egui::Key::ArrowLeft),
+                ),
+                (
+                    GamepadButton::DPadRight,
+                    EguiGamepadKey::new(egui::Key::ArrowRight),
+                ),
+                (GamepadButton::South, EguiGamepadKey::new(egui::Key::Enter)),
+                (GamepadButton::East, EguiGamepadKey::new(egui::Key::Escape)),
+                (
+                    GamepadButton::RightTrigger,
+                    EguiGamepadKey::new(egui::Key::Tab),
+                ),
+                (
+                    GamepadButton::LeftTrigger,
+                    EguiGamepadKey::with_modifiers(egui::Key::Tab, Modifiers::SHIFT),
+                ),
+            ]),
+            left_stick_navigation: true,
+            left_stick_threshold: 0.5,
+            right_stick_scroll_speed: 1000.0,
+            right_stick_dead_zone: 0.15,
+        }
+    }
+}
+
+/// Reads [`GamepadButtonStateChangedEvent`] messages and [`Gamepad`] sticks, and wraps them into [`EguiInputEvent`]
+/// according to [`EguiGamepadMapping`].
+///
+/// Gamepads don't belong to a window, so the events are sent to the [`FocusedNonWindowEguiContext`] if it exists,
+/// or to the contexts of focused windows otherwise.
+#[allow(clippy::too_many_arguments)]
+pub fn write_gamepad_messages_system(
+    mapping: Res<EguiGamepadMapping>,
+    mut gamepad_button_reader: MessageReader<GamepadButtonStateChangedEvent>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    mut left_stick_keys: Local<HashMap<Entity, egui::Key>>,
+    time: Res<Time<Real>>,
+    focused_non_window_egui_context: Option<Res<FocusedNonWindowEguiContext>>,
+    windows: Query<(Entity, &Window)>,
+    map: Res<WindowToEguiContextMap>,
+    egui_contexts: Query<&EguiContextSettings, With<EguiContext>>,
+    mut egui_input_message_writer: MessageWriter<EguiInputEvent>,
+) {
+    let mut events = Vec::new();
+    let key_event = |key: EguiGamepadKey, pressed: bool| egui::Event::Key {
+        key: key.key,
+        physical_key: None,
+        pressed,
+        repeat: false,
+        modifiers: key.modifiers,
+    };
+
+    for message in gamepad_button_reader.read() {
+        if let Some(&key) = mapping.buttons.get(&message.button) {
+            events.push(key_event(key, message.state.is_pressed()));
+        }
+    }
+
+    left_stick_keys.retain(|gamepad, _| gamepads.contains(*gamepad));
+    for (gamepad_entity, gamepad) in &gamepads {
+        let stick = gamepad.left_stick();
+        let stick_key = if !mapping.left_stick_navigation
+            || stick.abs().max_element() < mapping.left_stick_threshold
+        {
+            None
+        } else if stick.x.abs() > stick.y.abs() {
+            Some(if stick.x > 0.0 {
+                egui::Key::ArrowRight
+            } else {
+                egui::Key::ArrowLeft
+            })
+        } else {
+            Some(if stick.y > 0.0 {
+                egui::Key::ArrowUp
+            } else {
+                egui::Key::ArrowDown
+            })
+        };
+        let previous_key = left_stick_keys.get(&gamepad_entity).copied();
+        if stick_key != previous_key {
+            if let Some(key) = previous_key {
+                events.push(key_event(EguiGamepadKey::new(key), false));
+                left_stick_keys.remove(&gamepad_entity);
+            }
+            if let Some(key) = stick_key {
+                events.push(key_event(EguiGamepadKey::new(key), true));
+                left_stick_keys.insert(gamepad_entity, key);
+            }
+        }
+
+        let stick = gamepad.right_stick();
+        if mapping.right_stick_scroll_speed != 0.0
+            && stick.abs().max_element() >= mapping.right_stick_dead_zone
+        {
+            // Pushing the stick up scrolls up, pushing it right scrolls right (as a mouse wheel would).
+            let delta = egui::vec2(-stick.x, stick.y)
+                * mapping.right_stick_scroll_speed
+                * time.delta_secs();
+            events.push(egui::Event::MouseWheel {
+                unit: egui::MouseWheelUnit::Point,
+                delta,
+                modifiers: Modifiers::NONE,
+                phase: TouchPhase::Move,
+            });
+        }
+    }
+
+    if events.is_empty() {
+        return;
+    }
+
+    let contexts: Vec<Entity> = match focused_non_window_egui_context {
+        Some(context) => vec![context.0],
+        None => focused_window_contexts(&windows, &map).collect(),
+    };
+    for context in contexts {
+        let Ok(context_settings) = egui_contexts.get(context) else {
+            continue;
+        };
+        if !context_settings
+            .input_system_settings
+            .run_write_gamepad_messages_system
+        {
+            continue;
+        }
+
+        for event in &events {
+            egui_input_message_writer.write(EguiInputEvent {
+                context,
+                source: EguiInputSource::Gamepad,
+                event: event.clone(),
+            });
+        }
+    }
+}
+
+/// Reads [`Ime`] messages and wraps them into [`EguiInputEvent`], can redirect messages to [`FocusedNonWindowEguiContext`].
+pub fn write_ime_messages_system(
+    mut ime_reader: EguiContextMessageReader<Ime>,
+    mut egui_input_message_writer: MessageWriter<EguiInputEvent>,
+    mut egui_contexts: Query<
+        (
+            Entity,
+            &EguiContextSettings,
+            &mut EguiContextImeState,
+            &EguiOutput,
+        ),
         With<EguiContext>,
     >,
 ) {
@@ -816,6 +1705,7 @@ pub fn write_ime_messages_system(
             } => {
                 egui_input_message_writer.write(EguiInputEvent {
                     context,
+                    source: EguiInputSource::Ime,
                     event: egui::Event::Ime(egui::ImeEvent::Preedit {
                         text: value.clone(),
                         active_range_chars: cursor.map(|(start, end)| start..end),
@@ -825,6 +1715,7 @@ pub fn write_ime_messages_system(
             Ime::Commit { value, window: _ } => {
                 egui_input_message_writer.write(EguiInputEvent {
                     context,
+                    source: EguiInputSource::Ime,
                     event: egui::Event::Ime(egui::ImeEvent::Commit(value.clone())),
                 });
             }
@@ -837,6 +1728,9 @@ pub fn write_ime_messages_system(
 
 /// Show the virtual keyboard when a text input is focused.
 /// Works by reading [`EguiOutput`] and calling `Window::set_ime_allowed` if the `ime` field is set.
+///
+/// Only window contexts are handled, see `picking::process_world_space_ime_system` for world-space ones.
+/// IME is allowed in a window while any of its contexts needs it (see [`EguiWindowImeState`]).
 pub fn process_ime_system(
     mut egui_context_query: Query<(
         Entity,
@@ -847,6 +1741,7 @@ pub fn process_ime_system(
         &mut EguiContextImeState,
     )>,
     window_to_egui_context_map: Res<WindowToEguiContextMap>,
+    mut window_ime_state: ResMut<EguiWindowImeState>,
     _non_send_marker: NonSendMarker,
 ) {
     for (
@@ -858,52 +1753,50 @@ pub fn process_ime_system(
         mut egui_ime_state,
     ) in &mut egui_context_query
     {
-        if !egui_settings.enable_ime {
-            continue;
-        }
-
-        let Some(window_entity) = window_to_egui_context_map.context_to_window.get(&entity) else {
+        let Some(&window_entity) = window_to_egui_context_map.context_to_window.get(&entity) else {
             continue;
         };
 
-        bevy_winit::WINIT_WINDOWS.with_borrow_mut(|winit_windows| {
-            let Some(winit_window) = winit_windows.get_window(*window_entity) else {
-                log::warn!(
-                    "Cannot access an underlying winit window for a window entity {}",
-                    window_entity
-                );
+        let ime = egui_output
+            .platform_output
+            .ime
+            .filter(|_| egui_settings.enable_ime);
+        window_ime_state.set_context_window(entity, ime.map(|_| window_entity));
+        egui_ime_state.is_ime_allowed = ime.is_some();
 
-                return;
-            };
+        let Some(ime) = ime else {
+            egui_ime_state.ime_rect = None;
+            continue;
+        };
+        let ime_rect_px = ime.rect * zoom_factor;
+        if egui_ime_state.ime_rect != Some(ime_rect_px)
+            || egui_context.get_mut().input(|i| !i.events.is_empty())
+        {
+            egui_ime_state.ime_rect = Some(ime_rect_px);
+            bevy_winit::WINIT_WINDOWS.with_borrow(|winit_windows| {
+                let Some(winit_window) = winit_windows.get_window(window_entity) else {
+                    log::warn!(
+                        "Cannot access an underlying winit window for a window entity {}",
+                        window_entity
+                    );
 
-            let ime_allowed = egui_output.platform_output.ime.is_some();
-            if ime_allowed != egui_ime_state.is_ime_allowed {
-                winit_window.set_ime_allowed(ime_allowed);
-                egui_ime_state.is_ime_allowed = ime_allowed;
-            }
+                    return;
+                };
 
-            if let Some(ime) = egui_output.platform_output.ime {
-                let ime_rect_px = ime.rect * zoom_factor;
-                if egui_ime_state.ime_rect != Some(ime_rect_px)
-                    || egui_context.get_mut().input(|i| !i.events.is_empty())
-                {
-                    egui_ime_state.ime_rect = Some(ime_rect_px);
-                    winit_window.set_ime_cursor_area(
-                        winit::dpi::LogicalPosition {
-                            x: ime_rect_px.min.x,
-                            y: ime_rect_px.min.y,
-                        },
-                        winit::dpi::LogicalSize {
-                            width: ime_rect_px.width(),
-                            height: ime_rect_px.height(),
-                        },
-                    );
-                }
-            } else {
-                egui_ime_state.ime_rect = None;
-            }
-        });
+                winit_window.set_ime_cursor_area(
+                    winit::dpi::LogicalPosition {
+                        x: ime_rect_px.min.x,
+                        y: ime_rect_px.min.y,
+                    },
+                    winit::dpi::LogicalSize {
+                        width: ime_rect_px.width(),
+                        height: ime_rect_px.height(),
+                    },
+                );
+            });
+        }
     }
+    window_ime_state.retain_contexts(|context| egui_context_query.contains(context));
 }
 
 /// Reads [`FileDragAndDrop`] messages and wraps them into [`EguiFileDragAndDropMessage`], can redirect messages to [`HoveredNonWindowEguiContext`].
@@ -961,7 +1854,8 @@ pub fn write_file_dnd_messages_system(
 pub fn write_window_touch_messages_system(
     mut commands: Commands,
     egui_global_settings: Res<EguiGlobalSettings>,
-    modifier_keys_state: Res<ModifierKeysState>,
+    window_modifier_keys_state: Res<WindowModifierKeysState>,
+    time: Res<Time<Real>>,
     mut touch_input_reader: EguiContextMessageReader<TouchInput>,
     mut egui_input_message_writer: MessageWriter<EguiInputEvent>,
     mut egui_contexts: Query<
@@ -970,23 +1864,27 @@ pub fn write_window_touch_messages_system(
             &EguiContextSettings,
             &mut EguiContextPointerPosition,
             &mut EguiContextPointerTouchId,
+            &mut EguiPenState,
             &EguiOutput,
         ),
         With<EguiContext>,
     >,
 ) {
-    let modifiers = modifier_keys_state.to_egui_modifiers();
     let hovered_non_window_egui_context = touch_input_reader
         .hovered_non_window_egui_context
         .as_deref()
         .cloned();
 
     for (message, context) in touch_input_reader.read(|message| message.window) {
+        let modifiers = window_modifier_keys_state
+            .get(message.window)
+            .to_egui_modifiers();
         let Ok((
             &EguiZoomFactor { zoom_factor },
             context_settings,
             mut context_pointer_position,
             mut context_pointer_touch_id,
+            pen_state,
             output,
         )) = egui_contexts.get_mut(context)
         else {
@@ -1020,12 +1918,16 @@ pub fn write_window_touch_messages_system(
         context_pointer_position.position = touch_position;
         write_touch_message(
             &mut egui_input_message_writer,
+            EguiInputSource::WindowTouch,
             message,
             context,
             output,
             touch_position,
             modifiers,
             &mut context_pointer_touch_id,
+            pen_state,
+            context_settings.long_press.as_ref(),
+            time.elapsed(),
         );
     }
 }
@@ -1035,18 +1937,19 @@ pub fn write_non_window_touch_messages_system(
     focused_non_window_egui_context: Option<Res<FocusedNonWindowEguiContext>>,
     mut touch_input_reader: MessageReader<TouchInput>,
     mut egui_input_message_writer: MessageWriter<EguiInputEvent>,
-    modifier_keys_state: Res<ModifierKeysState>,
+    window_modifier_keys_state: Res<WindowModifierKeysState>,
+    time: Res<Time<Real>>,
     mut egui_contexts: Query<
         (
             &EguiContextSettings,
             &EguiContextPointerPosition,
             &mut EguiContextPointerTouchId,
+            &mut EguiPenState,
             &EguiOutput,
         ),
         With<EguiContext>,
     >,
 ) {
-    let modifiers = modifier_keys_state.to_egui_modifiers();
     for message in touch_input_reader.read() {
         let Some(&FocusedNonWindowEguiContext(focused_non_window_egui_context)) =
             focused_non_window_egui_context.as_deref()
@@ -1054,8 +1957,13 @@ pub fn write_non_window_touch_messages_system(
             continue;
         };
 
-        let Ok((context_settings, context_pointer_position, mut context_pointer_touch_id, output)) =
-            egui_contexts.get_mut(focused_non_window_egui_context)
+        let Ok((
+            context_settings,
+            context_pointer_position,
+            mut context_pointer_touch_id,
+            pen_state,
+            output,
+        )) = egui_contexts.get_mut(focused_non_window_egui_context)
         else {
             continue;
         };
@@ -1069,30 +1977,133 @@ pub fn write_non_window_touch_messages_system(
 
         write_touch_message(
             &mut egui_input_message_writer,
+            EguiInputSource::NonWindowTouch,
             message,
             focused_non_window_egui_context,
             output,
             context_pointer_position.position,
-            modifiers,
+            window_modifier_keys_state
+                .get(message.window)
+                .to_egui_modifiers(),
             &mut context_pointer_touch_id,
+            pen_state,
+            context_settings.long_press.as_ref(),
+            time.elapsed(),
+        );
+    }
+}
+
+/// Turns touches held in place for [`EguiLongPressSettings::duration`] into secondary button clicks,
+/// which open context menus (see [`egui::Response::context_menu`]).
+///
+/// The long press cancels the primary button click of the touch,
+/// and the emulated pointer doesn't follow the touch until it ends.
+pub fn write_long_press_messages_system(
+    modifier_keys_state: Res<ModifierKeysState>,
+    window_modifier_keys_state: Res<WindowModifierKeysState>,
+    map: Res<WindowToEguiContextMap>,
+    time: Res<Time<Real>>,
+    mut egui_contexts: Query<(Entity, &EguiContextSettings, &mut EguiContextPointerTouchId)>,
+    mut egui_input_message_writer: MessageWriter<EguiInputEvent>,
+) {
+    for (context, context_settings, mut context_pointer_touch_id) in &mut egui_contexts {
+        if !context_settings
+            .input_system_settings
+            .run_write_long_press_messages_system
+        {
+            continue;
+        }
+        let Some(long_press) = &context_settings.long_press else {
+            continue;
+        };
+        if context_pointer_touch_id.frozen_pointer_position.is_some() {
+            continue;
+        }
+        let Some((start_time, _)) = context_pointer_touch_id.long_press_start else {
+            continue;
+        };
+        if time.elapsed() - start_time < long_press.duration {
+            continue;
+        }
+        let Some(&position) = context_pointer_touch_id
+            .pointer_touch_id
+            .and_then(|id| context_pointer_touch_id.touch_positions.get(&id))
+        else {
+            continue;
+        };
+
+        context_pointer_touch_id.long_press_start = None;
+        context_pointer_touch_id.frozen_pointer_position = Some(position);
+        // Egui has no event to cancel a press, so the pointer is moved far away and back
+        // to make it not a click (it also stops Egui from detecting its own long touch).
+        for position in [position + egui::vec2(1.0e6, 0.0), position] {
+            egui_input_message_writer.write(EguiInputEvent {
+                context,
+                source: EguiInputSource::LongPress,
+                event: egui::Event::PointerMoved(position),
+            });
+        }
+        let modifiers = context_modifiers(
+            context,
+            &map,
+            &window_modifier_keys_state,
+            &modifier_keys_state,
         );
+        for pressed in [true, false] {
+            egui_input_message_writer.write(EguiInputEvent {
+                context,
+                source: EguiInputSource::LongPress,
+                event: egui::Event::PointerButton {
+                    pos: position,
+                    button: egui::PointerButton::Secondary,
+                    pressed,
+                    modifiers,
+                },
+            });
+        }
+    }
+}
+
+/// Stores changed [`EguiPenState`] components in the data of their Egui contexts, see [`EguiPenState::from_ctx`].
+pub fn write_pen_state_system(
+    mut egui_contexts: Query<(&mut EguiContext, &EguiPenState), Changed<EguiPenState>>,
+) {
+    for (mut context, pen_state) in &mut egui_contexts {
+        context
+            .get_mut()
+            .data_mut(|data| data.insert_temp(EguiPenState::id(), pen_state.clone()));
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn write_touch_message(
     egui_input_message_writer: &mut MessageWriter<EguiInputEvent>,
+    source: EguiInputSource,
     message: &TouchInput,
     context: Entity,
     _output: &EguiOutput,
     pointer_position: egui::Pos2,
     modifiers: Modifiers,
     context_pointer_touch_id: &mut EguiContextPointerTouchId,
+    mut pen_state: Mut<EguiPenState>,
+    long_press: Option<&EguiLongPressSettings>,
+    time: std::time::Duration,
 ) {
     let touch_id = egui::TouchId::from(message.id);
+    let force = match message.force {
+        Some(bevy_input::touch::ForceTouch::Normalized(force)) => Some(force as f32),
+        Some(bevy_input::touch::ForceTouch::Calibrated {
+            force,
+            max_possible_force,
+            ..
+        }) => Some((force / max_possible_force) as f32),
+        None => None,
+    };
 
     // Emit the touch message.
     egui_input_message_writer.write(EguiInputEvent {
         context,
+        source,
         event: egui::Event::Touch {
             device_id: egui::TouchDeviceId(message.window.to_bits()),
             id: touch_id,
@@ -1103,35 +2114,97 @@ fn write_touch_message(
                 bevy_input::touch::TouchPhase::Canceled => egui::TouchPhase::Cancel,
             },
             pos: pointer_position,
-            force: match message.force {
-                Some(bevy_input::touch::ForceTouch::Normalized(force)) => Some(force as f32),
-                Some(bevy_input::touch::ForceTouch::Calibrated {
-                    force,
-                    max_possible_force,
-                    ..
-                }) => Some((force / max_possible_force) as f32),
-                None => None,
-            },
+            force,
         },
     });
 
+    // Two or more touches scroll: each of them moves the centroid by its delta divided by the number of touches.
+    // Like trackpads, the scrolling has start and end phases, so that Egui applies deltas without smoothing.
+    let touch_positions = &mut context_pointer_touch_id.touch_positions;
+    let was_scrolling = touch_positions.len() > 1;
+    let previous_position = match message.phase {
+        bevy_input::touch::TouchPhase::Started | bevy_input::touch::TouchPhase::Moved => {
+            touch_positions.insert(message.id, pointer_position)
+        }
+        bevy_input::touch::TouchPhase::Ended | bevy_input::touch::TouchPhase::Canceled => {
+            touch_positions.remove(&message.id)
+        }
+    };
+    let touch_count = touch_positions.len();
+    let scroll = match (was_scrolling, touch_count > 1, message.phase) {
+        (false, true, _) => Some((TouchPhase::Start, egui::Vec2::ZERO)),
+        (true, false, _) => Some((TouchPhase::End, egui::Vec2::ZERO)),
+        (true, true, bevy_input::touch::TouchPhase::Moved) => previous_position.map(|position| {
+            (
+                TouchPhase::Move,
+                (pointer_position - position) / touch_count as f32,
+            )
+        }),
+        _ => None,
+    };
+    if let Some((phase, delta)) = scroll {
+        egui_input_message_writer.write(EguiInputEvent {
+            context,
+            source,
+            event: egui::Event::MouseWheel {
+                unit: egui::MouseWheelUnit::Point,
+                delta,
+                phase,
+                modifiers,
+            },
+        });
+    }
+    if touch_count > 1
+        && context_pointer_touch_id.frozen_pointer_position.is_none()
+        && let Some(pointer_touch_id) = context_pointer_touch_id.pointer_touch_id
+    {
+        context_pointer_touch_id.frozen_pointer_position = context_pointer_touch_id
+            .touch_positions
+            .get(&pointer_touch_id)
+            .copied();
+    }
+
     // If we're not yet translating a touch, or we're translating this very
     // touch, …
     if context_pointer_touch_id.pointer_touch_id.is_none()
         || context_pointer_touch_id.pointer_touch_id.unwrap() == message.id
     {
-        // … emit PointerButton resp. PointerMoved messages to emulate mouse.
+        // … track its pressure and tilt, …
+        pen_state.set_if_neq(match message.phase {
+            bevy_input::touch::TouchPhase::Started | bevy_input::touch::TouchPhase::Moved => {
+                EguiPenState {
+                    touching: true,
+                    pressure: force,
+                    altitude: match message.force {
+                        Some(bevy_input::touch::ForceTouch::Calibrated {
+                            altitude_angle, ..
+                        }) => altitude_angle.map(|altitude| altitude as f32),
+                        _ => None,
+                    },
+                }
+            }
+            bevy_input::touch::TouchPhase::Ended | bevy_input::touch::TouchPhase::Canceled => {
+                EguiPenState::default()
+            }
+        });
+
+        // … and emit PointerButton resp. PointerMoved messages to emulate mouse.
         match message.phase {
             bevy_input::touch::TouchPhase::Started => {
                 context_pointer_touch_id.pointer_touch_id = Some(message.id);
+                context_pointer_touch_id.frozen_pointer_position = None;
+                context_pointer_touch_id.long_press_start =
+                    long_press.map(|_| (time, pointer_position));
                 // First move the pointer to the right location.
                 egui_input_message_writer.write(EguiInputEvent {
                     context,
+                    source,
                     event: egui::Event::PointerMoved(pointer_position),
                 });
                 // Then do mouse button input.
                 egui_input_message_writer.write(EguiInputEvent {
                     context,
+                    source,
                     event: egui::Event::PointerButton {
                         pos: pointer_position,
                         button: egui::PointerButton::Primary,
@@ -1141,17 +2214,43 @@ fn write_touch_message(
                 });
             }
             bevy_input::touch::TouchPhase::Moved => {
-                egui_input_message_writer.write(EguiInputEvent {
-                    context,
-                    event: egui::Event::PointerMoved(pointer_position),
-                });
+                if let Some(long_press) = long_press
+                    && let Some((_, start_position)) = context_pointer_touch_id.long_press_start
+                    && start_position.distance(pointer_position) > long_press.max_distance
+                {
+                    context_pointer_touch_id.long_press_start = None;
+                }
+                if context_pointer_touch_id.frozen_pointer_position.is_none() {
+                    egui_input_message_writer.write(EguiInputEvent {
+                        context,
+                        source,
+                        event: egui::Event::PointerMoved(pointer_position),
+                    });
+                }
             }
             bevy_input::touch::TouchPhase::Ended => {
                 context_pointer_touch_id.pointer_touch_id = None;
+                context_pointer_touch_id.long_press_start = None;
+                let mut release_position = pointer_position;
+                if let Some(frozen_pointer_position) =
+                    context_pointer_touch_id.frozen_pointer_position.take()
+                {
+                    // Moving to the touch position and back doesn't move widgets dragged by the pointer,
+                    // but lets Egui know that a touch that has moved during scrolling isn't a click.
+                    for position in [pointer_position, frozen_pointer_position] {
+                        egui_input_message_writer.write(EguiInputEvent {
+                            context,
+                            source,
+                            event: egui::Event::PointerMoved(position),
+                        });
+                    }
+                    release_position = frozen_pointer_position;
+                }
                 egui_input_message_writer.write(EguiInputEvent {
                     context,
+                    source,
                     event: egui::Event::PointerButton {
-                        pos: pointer_position,
+                        pos: release_position,
                         button: egui::PointerButton::Primary,
                         pressed: false,
                         modifiers,
@@ -1159,6 +2258,7 @@ fn write_touch_message(
                 });
                 egui_input_message_writer.write(EguiInputEvent {
                     context,
+                    source,
                     event: egui::Event::PointerGone,
                 });
 
@@ -1172,8 +2272,11 @@ fn write_touch_message(
             }
             bevy_input::touch::TouchPhase::Canceled => {
                 context_pointer_touch_id.pointer_touch_id = None;
+                context_pointer_touch_id.frozen_pointer_position = None;
+                context_pointer_touch_id.long_press_start = None;
                 egui_input_message_writer.write(EguiInputEvent {
                     context,
+                    source,
                     event: egui::Event::PointerGone,
                 });
             }
@@ -1185,6 +2288,7 @@ fn write_touch_message(
 mod tests {
     use super::*;
     use bevy_app::{App, Update};
+    use bevy_input::mouse::MouseButtonInput;
     use bevy_math::Vec2;
 
     fn app_with_pointer_input_system() -> App {
@@ -1193,6 +2297,7 @@ mod tests {
             .add_message::<EguiInputEvent>()
             .init_resource::<EguiGlobalSettings>()
             .init_resource::<ModifierKeysState>()
+            .init_resource::<WindowModifierKeysState>()
             .init_resource::<WindowToEguiContextMap>()
             .add_systems(Update, write_pointer_moved_and_button_messages_system);
         app
@@ -1277,6 +2382,7 @@ mod tests {
             Some(EguiInputEvent {
                 context: event_context,
                 event: egui::Event::PointerMoved(position),
+                ..
             }) if *event_context == context && *position == egui::pos2(10.0, 20.0)
         ));
         assert!(matches!(
@@ -1284,6 +2390,7 @@ mod tests {
             Some(EguiInputEvent {
                 context: event_context,
                 event: egui::Event::PointerButton { pos, pressed: true, .. },
+                ..
             }) if *event_context == context && *pos == egui::pos2(10.0, 20.0)
         ));
         assert!(matches!(
@@ -1291,6 +2398,7 @@ mod tests {
             Some(EguiInputEvent {
                 context: event_context,
                 event: egui::Event::PointerMoved(position),
+                ..
             }) if *event_context == context && *position == egui::pos2(30.0, 40.0)
         ));
         assert!(messages.next().is_none());
@@ -1332,6 +2440,7 @@ mod tests {
             Some(EguiInputEvent {
                 context: event_context,
                 event: egui::Event::PointerButton { pos, pressed: true, .. },
+                ..
             }) if *event_context == context && *pos == egui::pos2(5.0, 6.0)
         ));
         assert!(messages.next().is_none());
@@ -1367,6 +2476,7 @@ mod tests {
             Some(EguiInputEvent {
                 context: event_context,
                 event: egui::Event::PointerMoved(position),
+                ..
             }) if *event_context == context && *position == egui::pos2(10.0, 20.0)
         ));
         assert!(messages.next().is_none());
@@ -1411,6 +2521,7 @@ mod tests {
             Some(EguiInputEvent {
                 context: event_context,
                 event: egui::Event::PointerMoved(position),
+                ..
             }) if *event_context == window_context && *position == egui::pos2(10.0, 20.0)
         ));
         assert!(matches!(
@@ -1418,6 +2529,7 @@ mod tests {
             Some(EguiInputEvent {
                 context: event_context,
                 event: egui::Event::PointerButton { pos, pressed: true, .. },
+                ..
             }) if *event_context == non_window_context && *pos == egui::pos2(50.0, 60.0)
         ));
         assert!(messages.next().is_none());
@@ -1426,57 +2538,1558 @@ mod tests {
             non_window_context
         );
     }
-}
-
-/// Reads both [`EguiFileDragAndDropMessage`] and [`EguiInputEvent`] messages and feeds them to Egui.
-#[allow(clippy::too_many_arguments)]
-pub fn write_egui_input_system(
-    focused_non_window_egui_context: Option<Res<FocusedNonWindowEguiContext>>,
-    window_to_egui_context_map: Res<WindowToEguiContextMap>,
-    modifier_keys_state: Res<ModifierKeysState>,
-    mut egui_input_reader: MessageReader<EguiInputEvent>,
-    mut egui_file_dnd_message_reader: MessageReader<EguiFileDragAndDropMessage>,
-    mut egui_contexts: Query<(Entity, &mut EguiInput, &Camera)>,
-    windows: Query<&Window>,
-    time: Res<Time<Real>>,
-) {
-    for EguiInputEvent { context, event } in egui_input_reader.read() {
-        #[cfg(feature = "log_input_messages")]
-        log::warn!("{context:?}: {event:?}");
 
-        let (_entity, mut egui_input, _camera) = match egui_contexts.get_mut(*context) {
-            Ok(egui_input) => egui_input,
-            Err(err) => {
-                log::error!(
-                    "Failed to get an Egui context ({context:?}) for an event ({event:?}): {err:?}"
-                );
-                continue;
-            }
+    #[test]
+    fn pasting_clipboard_image_writes_image_pasted_message() {
+        let mut app = App::new();
+        app.add_message::<KeyboardInput>()
+            .add_message::<EguiInputEvent>()
+            .add_message::<EguiClipboardImagePasted>()
+            .init_resource::<WindowModifierKeysState>()
+            .init_resource::<WindowToEguiContextMap>()
+            .insert_resource(crate::EguiClipboard::new(
+                crate::clipboard::EguiInMemoryClipboard::default(),
+            ))
+            .add_systems(Update, write_keyboard_input_messages_system);
+        let window = app.world_mut().spawn_empty().id();
+        *app.world_mut()
+            .resource_mut::<WindowModifierKeysState>()
+            .get_mut(window) = ModifierKeysState {
+            ctrl: true,
+            win: true,
+            ..Default::default()
         };
+        let context = app.world_mut().spawn(EguiContext::default()).id();
+        app.world_mut()
+            .resource_mut::<WindowToEguiContextMap>()
+            .window_to_contexts
+            .entry(window)
+            .or_default()
+            .insert(context);
+        let image = egui::ColorImage::filled([2, 3], egui::Color32::GREEN);
+        app.world_mut()
+            .resource_mut::<crate::EguiClipboard>()
+            .set_image(&image);
 
-        egui_input.events.push(event.clone());
-    }
-
-    for EguiFileDragAndDropMessage { context, message } in egui_file_dnd_message_reader.read() {
-        #[cfg(feature = "log_file_dnd_messages")]
-        log::warn!("{context:?}: {message:?}");
+        app.world_mut().write_message(KeyboardInput {
+            key_code: KeyCode::KeyV,
+            logical_key: Key::Character("v".into()),
+            state: ButtonState::Pressed,
+            text: Some("v".into()),
+            repeat: false,
+            window,
+        });
+        app.update();
 
-        let (_entity, mut egui_input, _camera) = match egui_contexts.get_mut(*context) {
-            Ok(egui_input) => egui_input,
-            Err(err) => {
-                log::error!(
-                    "Failed to get an Egui context ({context:?}) for an message ({message:?}): {err:?}"
-                );
-                continue;
-            }
-        };
+        {
+            let messages = app.world().resource::<Messages<EguiClipboardImagePasted>>();
+            let mut messages = messages.iter_current_update_messages();
+            assert!(matches!(
+                messages.next(),
+                Some(EguiClipboardImagePasted {
+                    context: pasted_context,
+                    image: pasted_image,
+                }) if *pasted_context == context && *pasted_image == image
+            ));
+            assert!(messages.next().is_none());
+            let messages = app.world().resource::<Messages<EguiInputEvent>>();
+            assert!(
+                !messages
+                    .iter_current_update_messages()
+                    .any(|message| matches!(message.event, egui::Event::Text(_)))
+            );
+        }
 
-        match message {
-            FileDragAndDrop::DroppedFile {
-                window: _,
-                path_buf,
-            } => {
-                egui_input.hovered_files.clear();
+        // With several contexts in the window, the image is pasted once, to the one that wants keyboard input.
+        let focused_context = app
+            .world_mut()
+            .spawn((
+                EguiContext::default(),
+                EguiContextWantsInput(EguiWantsInput {
+                    wants_keyboard_input: true,
+                    ..Default::default()
+                }),
+            ))
+            .id();
+        app.world_mut()
+            .resource_mut::<WindowToEguiContextMap>()
+            .window_to_contexts
+            .entry(window)
+            .or_default()
+            .insert(focused_context);
+        app.world_mut().write_message(KeyboardInput {
+            key_code: KeyCode::KeyV,
+            logical_key: Key::Character("v".into()),
+            state: ButtonState::Pressed,
+            text: Some("v".into()),
+            repeat: false,
+            window,
+        });
+        app.update();
+
+        let messages = app.world().resource::<Messages<EguiClipboardImagePasted>>();
+        let pasted_contexts: Vec<_> = messages
+            .iter_current_update_messages()
+            .map(|message| message.context)
+            .collect();
+        assert_eq!(pasted_contexts, [focused_context]);
+    }
+
+    #[test]
+    fn mouse_motion_is_sent_to_focused_window_contexts() {
+        let mut app = App::new();
+        app.add_message::<MouseMotion>()
+            .add_message::<EguiInputEvent>()
+            .init_resource::<WindowToEguiContextMap>()
+            .add_systems(Update, write_mouse_motion_messages_system);
+        let window = app.world_mut().spawn(Window::default()).id();
+        let unfocused_window = app
+            .world_mut()
+            .spawn(Window {
+                focused: false,
+                ..Default::default()
+            })
+            .id();
+        // Gamepad navigation is opt-in.
+        let gamepad_settings = || EguiContextSettings {
+            input_system_settings: EguiInputSystemSettings {
+                run_write_gamepad_messages_system: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let context = app
+            .world_mut()
+            .spawn((EguiContext::default(), gamepad_settings()))
+            .id();
+        let unfocused_context = app
+            .world_mut()
+            .spawn((EguiContext::default(), gamepad_settings()))
+            .id();
+        let mut map = app.world_mut().resource_mut::<WindowToEguiContextMap>();
+        map.window_to_contexts
+            .entry(window)
+            .or_default()
+            .insert(context);
+        map.window_to_contexts
+            .entry(unfocused_window)
+            .or_default()
+            .insert(unfocused_context);
+
+        app.world_mut().write_message(MouseMotion {
+            delta: Vec2::new(3.0, -1.0),
+        });
+        app.world_mut().write_message(MouseMotion {
+            delta: Vec2::new(2.0, 4.0),
+        });
+        app.update();
+
+        let messages = app.world().resource::<Messages<EguiInputEvent>>();
+        let mut messages = messages.iter_current_update_messages();
+        assert!(matches!(
+            messages.next(),
+            Some(EguiInputEvent {
+                context: event_context,
+                event: egui::Event::MouseMoved(delta),
+                ..
+            }) if *event_context == context && *delta == egui::vec2(5.0, 3.0)
+        ));
+        assert!(messages.next().is_none());
+    }
+
+    #[test]
+    fn gamepad_input_navigates_focused_window_context() {
+        let mut app = App::new();
+        app.add_message::<GamepadButtonStateChangedEvent>()
+            .add_message::<EguiInputEvent>()
+            .init_resource::<EguiGamepadMapping>()
+            .init_resource::<Time<Real>>()
+            .init_resource::<WindowToEguiContextMap>()
+            .add_systems(Update, write_gamepad_messages_system);
+        let window = app.world_mut().spawn(Window::default()).id();
+        let unfocused_window = app
+            .world_mut()
+            .spawn(Window {
+                focused: false,
+                ..Default::default()
+            })
+            .id();
+        // Gamepad navigation is opt-in.
+        let gamepad_settings = || EguiContextSettings {
+            input_system_settings: EguiInputSystemSettings {
+                run_write_gamepad_messages_system: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let context = app
+            .world_mut()
+            .spawn((EguiContext::default(), gamepad_settings()))
+            .id();
+        let unfocused_context = app
+            .world_mut()
+            .spawn((EguiContext::default(), gamepad_settings()))
+            .id();
+        let mut map = app.world_mut().resource_mut::<WindowToEguiContextMap>();
+        map.window_to_contexts
+            .entry(window)
+            .or_default()
+            .insert(context);
+        map.window_to_contexts
+            .entry(unfocused_window)
+            .or_default()
+            .insert(unfocused_context);
+        let gamepad = app.world_mut().spawn(Gamepad::default()).id();
+        let set_left_stick_x = |app: &mut App, value: f32| {
+            app.world_mut()
+                .get_mut::<Gamepad>(gamepad)
+                .unwrap()
+                .analog_mut()
+                .set(bevy_input::gamepad::GamepadAxis::LeftStickX, value);
+        };
+        let keys = |app: &App| {
+            app.world()
+                .resource::<Messages<EguiInputEvent>>()
+                .iter_current_update_messages()
+                .map(|message| match message.event {
+                    egui::Event::Key {
+                        key,
+                        pressed,
+                        modifiers,
+                        ..
+                    } => {
+                        assert_eq!(message.context, context);
+                        (key, pressed, modifiers)
+                    }
+                    ref event => panic!("unexpected event: {event:?}"),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        app.world_mut()
+            .write_message(GamepadButtonStateChangedEvent::new(
+                gamepad,
+                GamepadButton::LeftTrigger,
+                ButtonState::Pressed,
+            ));
+        set_left_stick_x(&mut app, 0.9);
+        app.update();
+        assert_eq!(
+            keys(&app),
+            [
+                (egui::Key::Tab, true, Modifiers::SHIFT),
+                (egui::Key::ArrowRight, true, Modifiers::NONE),
+            ]
+        );
+
+        // Holding the stick doesn't repeat the key.
+        app.update();
+        assert_eq!(keys(&app), []);
+
+        set_left_stick_x(&mut app, 0.0);
+        app.update();
+        assert_eq!(
+            keys(&app),
+            [(egui::Key::ArrowRight, false, Modifiers::NONE)]
+        );
+
+        app.world_mut()
+            .get_mut::<EguiContextSettings>(context)
+            .unwrap()
+            .input_system_settings
+            .run_write_gamepad_messages_system = false;
+        app.world_mut()
+            .write_message(GamepadButtonStateChangedEvent::new(
+                gamepad,
+                GamepadButton::South,
+                ButtonState::Pressed,
+            ));
+        app.update();
+        assert_eq!(keys(&app), []);
+    }
+
+    #[test]
+    fn input_filters_modify_drop_and_inject_events() {
+        let mut app = App::new();
+        app.add_message::<EguiInputEvent>()
+            .add_message::<EguiFileDragAndDropMessage>()
+            .add_message::<WindowThemeChanged>()
+            .init_resource::<ModifierKeysState>()
+            .init_resource::<WindowModifierKeysState>()
+            .init_resource::<WindowToEguiContextMap>()
+            .init_resource::<Time<Real>>()
+            .add_systems(Update, write_egui_input_system);
+        let filters = EguiInputFilters::default()
+            .with(|events: &mut Vec<egui::Event>| {
+                events.retain(|event| {
+                    !matches!(
+                        event,
+                        egui::Event::Key {
+                            key: egui::Key::Tab,
+                            ..
+                        }
+                    )
+                });
+            })
+            .with(|events: &mut Vec<egui::Event>| {
+                for event in events.iter_mut() {
+                    if let egui::Event::ModifiersChanged(modifiers) = event {
+                        modifiers.mac_cmd = modifiers.ctrl;
+                    }
+                }
+                events.push(egui::Event::Text("injected".to_owned()));
+            });
+        let context = app
+            .world_mut()
+            .spawn((EguiContext::default(), Camera::default(), filters))
+            .id();
+        app.world_mut().resource_mut::<ModifierKeysState>().ctrl = true;
+        let key = |key| egui::Event::Key {
+            key,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: egui::Modifiers::NONE,
+        };
+        for event in [key(egui::Key::Tab), key(egui::Key::A)] {
+            app.world_mut().write_message(EguiInputEvent {
+                context,
+                source: EguiInputSource::Custom,
+                event,
+            });
+        }
+        let modifiers = egui::Modifiers {
+            ctrl: true,
+            mac_cmd: true,
+            ..app
+                .world()
+                .resource::<ModifierKeysState>()
+                .to_egui_modifiers()
+        };
+        let expected_events = [
+            key(egui::Key::A),
+            egui::Event::ModifiersChanged(modifiers),
+            egui::Event::Text("injected".to_owned()),
+        ];
+        app.update();
+        assert_eq!(
+            app.world().get::<EguiInput>(context).unwrap().events,
+            expected_events
+        );
+
+        // Events that haven't been taken by Egui yet aren't filtered twice.
+        app.update();
+        let events = &app.world().get::<EguiInput>(context).unwrap().events;
+        assert_eq!(events[..3], expected_events);
+        assert_eq!(events[3..], expected_events[1..]);
+    }
+
+    #[test]
+    fn modifiers_are_tracked_per_window() {
+        let mut app = App::new();
+        app.add_message::<KeyboardInput>()
+            .add_message::<KeyboardFocusLost>()
+            .add_message::<WindowFocused>()
+            .add_message::<EguiInputEvent>()
+            .add_message::<EguiClipboardImagePasted>()
+            .add_message::<EguiFileDragAndDropMessage>()
+            .add_message::<WindowThemeChanged>()
+            .init_resource::<ModifierKeysState>()
+            .init_resource::<WindowModifierKeysState>()
+            .init_resource::<WindowToEguiContextMap>()
+            .init_resource::<Time<Real>>()
+            .insert_resource(crate::EguiClipboard::new(
+                crate::clipboard::EguiInMemoryClipboard::default(),
+            ))
+            .add_systems(
+                Update,
+                (
+                    write_modifiers_keys_state_system,
+                    write_keyboard_input_messages_system,
+                    write_egui_input_system,
+                )
+                    .chain(),
+            );
+        let [(window_a, context_a), (window_b, context_b)] = [(); 2].map(|_| {
+            let window = app.world_mut().spawn_empty().id();
+            let context = app
+                .world_mut()
+                .spawn((EguiContext::default(), Camera::default()))
+                .id();
+            let mut map = app.world_mut().resource_mut::<WindowToEguiContextMap>();
+            map.window_to_contexts
+                .entry(window)
+                .or_default()
+                .insert(context);
+            map.context_to_window.insert(context, window);
+            (window, context)
+        });
+        let keyboard_input = |window, key: Key, pressed| KeyboardInput {
+            key_code: KeyCode::Unidentified(bevy_input::keyboard::NativeKeyCode::Unidentified),
+            logical_key: key,
+            state: if pressed {
+                ButtonState::Pressed
+            } else {
+                ButtonState::Released
+            },
+            text: None,
+            repeat: false,
+            window,
+        };
+        let take_events = |app: &mut App, context| {
+            std::mem::take(
+                &mut app
+                    .world_mut()
+                    .get_mut::<EguiInput>(context)
+                    .unwrap()
+                    .events,
+            )
+        };
+        let text = |event: &egui::Event| match event {
+            egui::Event::Text(text) => Some(text.clone()),
+            _ => None,
+        };
+        let modifiers = |events: &[egui::Event]| {
+            events.iter().find_map(|event| match event {
+                egui::Event::ModifiersChanged(modifiers) => Some(*modifiers),
+                _ => None,
+            })
+        };
+
+        // Holding Ctrl in one window doesn't affect the other one.
+        app.world_mut()
+            .write_message(keyboard_input(window_a, Key::Control, true));
+        for window in [window_a, window_b] {
+            app.world_mut()
+                .write_message(keyboard_input(window, Key::Character("b".into()), true));
+        }
+        app.update();
+        let events_a = take_events(&mut app, context_a);
+        let events_b = take_events(&mut app, context_b);
+        assert_eq!(
+            modifiers(&events_a),
+            Some(
+                ModifierKeysState {
+                    ctrl: true,
+                    ..Default::default()
+                }
+                .to_egui_modifiers()
+            )
+        );
+        assert_eq!(modifiers(&events_b), Some(egui::Modifiers::NONE));
+        assert_eq!(events_a.iter().filter_map(text).count(), 0);
+        assert_eq!(
+            events_b.iter().filter_map(text).collect::<Vec<_>>(),
+            vec!["b".to_owned()]
+        );
+
+        // Losing focus clears modifiers of the window.
+        app.world_mut().write_message(WindowFocused {
+            window: window_a,
+            focused: false,
+        });
+        app.world_mut()
+            .write_message(keyboard_input(window_a, Key::Character("c".into()), true));
+        app.update();
+        let events_a = take_events(&mut app, context_a);
+        assert_eq!(modifiers(&events_a), Some(egui::Modifiers::NONE));
+        assert_eq!(
+            events_a.iter().filter_map(text).collect::<Vec<_>>(),
+            vec!["c".to_owned()]
+        );
+    }
+
+    #[test]
+    fn mouse_wheel_uses_modifiers_of_its_window() {
+        let mut app = App::new();
+        app.add_message::<MouseWheel>()
+            .add_message::<EguiInputEvent>()
+            .init_resource::<WindowModifierKeysState>()
+            .init_resource::<WindowToEguiContextMap>()
+            .add_systems(Update, write_mouse_wheel_messages_system);
+        let window_a = app.world_mut().spawn_empty().id();
+        let window_b = app.world_mut().spawn_empty().id();
+        let context_b = app.world_mut().spawn(EguiContext::default()).id();
+        app.world_mut()
+            .resource_mut::<WindowToEguiContextMap>()
+            .window_to_contexts
+            .entry(window_b)
+            .or_default()
+            .insert(context_b);
+        // Ctrl is held in another window.
+        app.world_mut()
+            .resource_mut::<WindowModifierKeysState>()
+            .get_mut(window_a)
+            .ctrl = true;
+        app.world_mut().write_message(MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.0,
+            y: 1.0,
+            window: window_b,
+            phase: bevy_input::touch::TouchPhase::Moved,
+        });
+        app.update();
+
+        let events = app
+            .world()
+            .resource::<Messages<EguiInputEvent>>()
+            .iter_current_update_messages()
+            .map(|message| message.event.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            [egui::Event::MouseWheel {
+                unit: egui::MouseWheelUnit::Line,
+                delta: egui::vec2(0.0, 1.0),
+                modifiers: egui::Modifiers::NONE,
+                phase: TouchPhase::Move,
+            }]
+        );
+    }
+
+    #[test]
+    fn keyboard_input_forwards_repeat_and_alt_gr_text() {
+        let mut app = App::new();
+        app.add_message::<KeyboardInput>()
+            .add_message::<EguiInputEvent>()
+            .add_message::<EguiClipboardImagePasted>()
+            .init_resource::<WindowModifierKeysState>()
+            .init_resource::<WindowToEguiContextMap>()
+            .insert_resource(crate::EguiClipboard::new(
+                crate::clipboard::EguiInMemoryClipboard::default(),
+            ))
+            .add_systems(Update, write_keyboard_input_messages_system);
+        let window = app.world_mut().spawn_empty().id();
+        let context = app.world_mut().spawn(EguiContext::default()).id();
+        app.world_mut()
+            .resource_mut::<WindowToEguiContextMap>()
+            .window_to_contexts
+            .entry(window)
+            .or_default()
+            .insert(context);
+        let send = |app: &mut App, state, key_code, key: &str, repeat| {
+            *app.world_mut()
+                .resource_mut::<WindowModifierKeysState>()
+                .get_mut(window) = state;
+            app.world_mut().write_message(KeyboardInput {
+                key_code,
+                logical_key: Key::Character(key.into()),
+                state: ButtonState::Pressed,
+                text: Some(key.into()),
+                repeat,
+                window,
+            });
+            app.update();
+            app.world()
+                .resource::<Messages<EguiInputEvent>>()
+                .iter_current_update_messages()
+                .map(|message| message.event.clone())
+                .collect::<Vec<_>>()
+        };
+        let key = |key, modifiers, repeat| egui::Event::Key {
+            key,
+            physical_key: Some(key),
+            pressed: true,
+            repeat,
+            modifiers,
+        };
+
+        // Windows reports AltGr as Ctrl + AltGraph, typing text isn't a shortcut.
+        let alt_gr = ModifierKeysState {
+            ctrl: true,
+            alt_gr: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            send(&mut app, alt_gr, KeyCode::KeyQ, "@", false),
+            vec![
+                egui::Event::Text("@".to_owned()),
+                key(egui::Key::Q, egui::Modifiers::NONE, false),
+            ]
+        );
+        assert_eq!(
+            send(&mut app, alt_gr, KeyCode::KeyQ, "@", true),
+            vec![
+                egui::Event::Text("@".to_owned()),
+                key(egui::Key::Q, egui::Modifiers::NONE, true),
+            ]
+        );
+
+        // Ctrl alone is still a shortcut.
+        let ctrl = ModifierKeysState {
+            ctrl: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            send(&mut app, ctrl, KeyCode::KeyZ, "z", false),
+            vec![key(egui::Key::Z, ctrl.to_egui_modifiers(), false)]
+        );
+
+        // Ctrl + Alt that types the unmodified character (US layouts) is a shortcut too.
+        let ctrl_alt = ModifierKeysState {
+            ctrl: true,
+            alt: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            send(&mut app, ctrl_alt, KeyCode::KeyZ, "z", false),
+            vec![key(egui::Key::Z, ctrl_alt.to_egui_modifiers(), false)]
+        );
+
+        // Windows reports AltGr as Ctrl + Alt on some layouts.
+        #[cfg(target_os = "windows")]
+        assert_eq!(
+            send(&mut app, ctrl_alt, KeyCode::KeyZ, "ż", false),
+            vec![
+                egui::Event::Text("ż".to_owned()),
+                key(egui::Key::Z, egui::Modifiers::NONE, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn ime_is_allowed_while_any_context_of_the_window_needs_it() {
+        let mut world = World::new();
+        let [window, window_context, world_space_context] =
+            std::array::from_fn(|_| world.spawn_empty().id());
+        let mut ime_state = EguiWindowImeState::default();
+
+        ime_state.set_context_window(window_context, Some(window));
+        ime_state.set_context_window(world_space_context, Some(window));
+        ime_state.set_context_window(window_context, None);
+        assert!(ime_state.is_ime_allowed(window));
+
+        // Forgetting the last context disallows IME.
+        ime_state.retain_contexts(|context| context != world_space_context);
+        assert!(!ime_state.is_ime_allowed(window));
+    }
+
+    #[test]
+    fn system_theme_follows_window_theme_unless_overridden() {
+        let mut app = App::new();
+        app.add_message::<EguiInputEvent>()
+            .add_message::<EguiFileDragAndDropMessage>()
+            .add_message::<WindowThemeChanged>()
+            .init_resource::<ModifierKeysState>()
+            .init_resource::<WindowModifierKeysState>()
+            .init_resource::<WindowToEguiContextMap>()
+            .init_resource::<Time<Real>>()
+            .add_systems(Update, write_egui_input_system);
+
+        let window = app
+            .world_mut()
+            .spawn(Window {
+                window_theme: Some(WindowTheme::Light),
+                ..Default::default()
+            })
+            .id();
+        let following_context = app
+            .world_mut()
+            .spawn((EguiContext::default(), Camera::default()))
+            .id();
+        let overridden_context = app
+            .world_mut()
+            .spawn((
+                EguiContext::default(),
+                Camera::default(),
+                EguiContextSettings {
+                    system_theme_override: Some(WindowTheme::Dark),
+                    ..Default::default()
+                },
+            ))
+            .id();
+        let mut map = app.world_mut().resource_mut::<WindowToEguiContextMap>();
+        for context in [following_context, overridden_context] {
+            map.window_to_contexts
+                .entry(window)
+                .or_default()
+                .insert(context);
+            map.context_to_window.insert(context, window);
+        }
+
+        let system_theme = |app: &App, context: Entity| {
+            app.world().get::<EguiInput>(context).unwrap().system_theme
+        };
+        app.update();
+        assert_eq!(
+            system_theme(&app, following_context),
+            Some(egui::Theme::Light)
+        );
+        assert_eq!(
+            system_theme(&app, overridden_context),
+            Some(egui::Theme::Dark)
+        );
+
+        app.world_mut().write_message(WindowThemeChanged {
+            window,
+            theme: WindowTheme::Dark,
+        });
+        app.update();
+        assert_eq!(
+            system_theme(&app, following_context),
+            Some(egui::Theme::Dark)
+        );
+
+        app.world_mut()
+            .get_mut::<EguiContextSettings>(overridden_context)
+            .unwrap()
+            .system_theme_override = Some(WindowTheme::Light);
+        app.update();
+        assert_eq!(
+            system_theme(&app, overridden_context),
+            Some(egui::Theme::Light)
+        );
+    }
+
+    #[test]
+    fn viewport_info_follows_window_and_monitor() {
+        use bevy_math::IVec2;
+
+        let mut app = App::new();
+        app.add_message::<WindowOccluded>()
+            .init_resource::<EguiGlobalSettings>()
+            .init_resource::<WindowToEguiContextMap>()
+            .add_systems(Update, write_viewport_info_system);
+
+        let monitor = app
+            .world_mut()
+            .spawn(Monitor {
+                name: None,
+                physical_height: 1080,
+                physical_width: 1920,
+                physical_position: IVec2::ZERO,
+                refresh_rate_millihertz: None,
+                scale_factor: 2.0,
+                video_modes: Vec::new(),
+            })
+            .id();
+        let mut window = Window {
+            title: "Viewport".to_owned(),
+            position: WindowPosition::At(IVec2::new(200, 100)),
+            focused: true,
+            ..Default::default()
+        };
+        window.resolution.set_scale_factor(2.0);
+        window.resolution.set(400.0, 300.0);
+        let window = app.world_mut().spawn((window, OnMonitor(monitor))).id();
+        let context = app
+            .world_mut()
+            .spawn((EguiContext::default(), EguiZoomFactor { zoom_factor: 2.0 }))
+            .id();
+        let mut map = app.world_mut().resource_mut::<WindowToEguiContextMap>();
+        map.window_to_contexts
+            .entry(window)
+            .or_default()
+            .insert(context);
+        map.context_to_window.insert(context, window);
+        app.update();
+
+        let viewport_info = |app: &App| {
+            app.world().get::<EguiInput>(context).unwrap().viewports[&egui::ViewportId::ROOT]
+                .clone()
+        };
+        let info = viewport_info(&app);
+        assert_eq!(info.title.as_deref(), Some("Viewport"));
+        assert_eq!(info.focused, Some(true));
+        assert_eq!(info.fullscreen, Some(false));
+        assert_eq!(
+            info.inner_rect,
+            Some(egui::Rect::from_min_size(
+                egui::pos2(50.0, 25.0),
+                egui::vec2(200.0, 150.0)
+            ))
+        );
+        assert_eq!(info.monitor_size, Some(egui::vec2(480.0, 270.0)));
+
+        app.world_mut()
+            .get_mut::<Window>(window)
+            .unwrap()
+            .resolution
+            .set(800.0, 600.0);
+        app.world_mut().write_message(WindowOccluded {
+            window,
+            occluded: true,
+        });
+        app.update();
+        let info = viewport_info(&app);
+        assert_eq!(info.inner_rect.unwrap().size(), egui::vec2(400.0, 300.0));
+        assert_eq!(info.occluded, Some(true));
+
+        // Unchanged windows don't override the info.
+        app.world_mut()
+            .get_mut::<EguiInput>(context)
+            .unwrap()
+            .viewports
+            .get_mut(&egui::ViewportId::ROOT)
+            .unwrap()
+            .title = None;
+        app.update();
+        assert_eq!(viewport_info(&app).title, None);
+    }
+
+    #[test]
+    fn input_is_absorbed_only_for_windows_that_want_it() {
+        use bevy_ecs::system::RunSystemOnce;
+
+        let mut app = App::new();
+        app.add_message::<KeyboardInput>()
+            .add_message::<MouseWheel>()
+            .add_message::<MouseButtonInput>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<EguiGlobalSettings>()
+            .init_resource::<WindowToEguiContextMap>()
+            .add_systems(Update, absorb_bevy_input_system);
+        let game_window = app.world_mut().spawn(Window::default()).id();
+        let editor_window = app.world_mut().spawn(Window::default()).id();
+        let game_context = app.world_mut().spawn(EguiContext::default()).id();
+        let editor_context = app
+            .world_mut()
+            .spawn((
+                EguiContext::default(),
+                EguiContextWantsInput(EguiWantsInput {
+                    wants_keyboard_input: true,
+                    ..Default::default()
+                }),
+            ))
+            .id();
+        let mut map = app.world_mut().resource_mut::<WindowToEguiContextMap>();
+        for (window, context) in [(game_window, game_context), (editor_window, editor_context)] {
+            map.window_to_contexts
+                .entry(window)
+                .or_default()
+                .insert(context);
+            map.context_to_window.insert(context, window);
+        }
+        let key_a = |window| KeyboardInput {
+            key_code: KeyCode::KeyA,
+            logical_key: Key::Character("a".into()),
+            state: ButtonState::Pressed,
+            text: None,
+            repeat: false,
+            window,
+        };
+        let keyboard_messages = |app: &App| app.world().resource::<Messages<KeyboardInput>>().len();
+
+        assert!(
+            app.world_mut()
+                .run_system_once(egui_wants_keyboard_input_for(editor_window))
+                .unwrap()
+        );
+        assert!(
+            !app.world_mut()
+                .run_system_once(egui_wants_keyboard_input_for(game_window))
+                .unwrap()
+        );
+        assert!(
+            !app.world_mut()
+                .run_system_once(egui_wants_pointer_input_for(editor_window))
+                .unwrap()
+        );
+
+        // The text field in the editor window doesn't block the game window.
+        app.world_mut()
+            .get_mut::<Window>(editor_window)
+            .unwrap()
+            .focused = false;
+        app.world_mut().write_message(key_a(game_window));
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyA);
+        app.update();
+        assert_eq!(keyboard_messages(&app), 1);
+        assert!(
+            app.world()
+                .resource::<ButtonInput<KeyCode>>()
+                .pressed(KeyCode::KeyA)
+        );
+
+        // Input of the editor window is absorbed.
+        app.world_mut()
+            .get_mut::<Window>(game_window)
+            .unwrap()
+            .focused = false;
+        app.world_mut()
+            .get_mut::<Window>(editor_window)
+            .unwrap()
+            .focused = true;
+        app.world_mut().write_message(key_a(editor_window));
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyA);
+        app.update();
+        assert!(
+            !app.world()
+                .resource::<ButtonInput<KeyCode>>()
+                .pressed(KeyCode::KeyA)
+        );
+
+        // Messages are kept, as readers of other windows may need them.
+        assert_eq!(keyboard_messages(&app), 1);
+        app.world_mut().write_message(key_a(game_window));
+        app.world_mut().write_message(key_a(editor_window));
+        app.update();
+        assert_eq!(keyboard_messages(&app), 2);
+    }
+
+    #[test]
+    fn touch_gesture_and_gamepad_input_is_absorbed() {
+        use bevy_input::touch::{TouchPhase, touch_screen_input_system};
+
+        let mut app = App::new();
+        app.add_message::<KeyboardInput>()
+            .add_message::<MouseWheel>()
+            .add_message::<MouseButtonInput>()
+            .add_message::<TouchInput>()
+            .add_message::<PinchGesture>()
+            .add_message::<GamepadButtonStateChangedEvent>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<Touches>()
+            .init_resource::<EguiGlobalSettings>()
+            .init_resource::<WindowToEguiContextMap>()
+            .add_systems(
+                Update,
+                (touch_screen_input_system, absorb_bevy_input_system).chain(),
+            );
+        let window = app.world_mut().spawn(Window::default()).id();
+        let context = app
+            .world_mut()
+            .spawn((
+                EguiContext::default(),
+                EguiContextWantsInput(EguiWantsInput {
+                    is_pointer_over_area: true,
+                    ..Default::default()
+                }),
+            ))
+            .id();
+        let mut map = app.world_mut().resource_mut::<WindowToEguiContextMap>();
+        map.window_to_contexts
+            .entry(window)
+            .or_default()
+            .insert(context);
+        map.context_to_window.insert(context, window);
+        let gamepad = app.world_mut().spawn(Gamepad::default()).id();
+
+        let write_input = |app: &mut App| {
+            app.world_mut().write_message(TouchInput {
+                phase: TouchPhase::Started,
+                position: Vec2::new(10.0, 10.0),
+                window,
+                force: None,
+                id: 0,
+            });
+            app.world_mut().write_message(PinchGesture(0.1));
+            app.world_mut()
+                .write_message(GamepadButtonStateChangedEvent::new(
+                    gamepad,
+                    GamepadButton::South,
+                    ButtonState::Pressed,
+                ));
+            app.world_mut()
+                .get_mut::<Gamepad>(gamepad)
+                .unwrap()
+                .digital_mut()
+                .press(GamepadButton::South);
+        };
+        let message_counts = |app: &App| {
+            let world = app.world();
+            (
+                world.resource::<Messages<TouchInput>>().len(),
+                world.resource::<Messages<PinchGesture>>().len(),
+                world
+                    .resource::<Messages<GamepadButtonStateChangedEvent>>()
+                    .len(),
+            )
+        };
+        let touches = |app: &App| app.world().resource::<Touches>().iter().count();
+        let gamepad_pressed = |app: &App| {
+            app.world()
+                .get::<Gamepad>(gamepad)
+                .unwrap()
+                .pressed(GamepadButton::South)
+        };
+
+        // Pointer input is absorbed (touch messages belong to a window and are kept),
+        // gamepad input isn't, as nothing has keyboard focus.
+        write_input(&mut app);
+        app.update();
+        assert_eq!(message_counts(&app), (1, 0, 1));
+        assert_eq!(touches(&app), 0);
+        assert!(gamepad_pressed(&app));
+
+        // Touch absorption can be disabled separately.
+        app.world_mut()
+            .resource_mut::<EguiGlobalSettings>()
+            .absorb_input_settings
+            .absorb_touch = false;
+        write_input(&mut app);
+        app.update();
+        assert_eq!(message_counts(&app), (1, 0, 1));
+        assert_eq!(touches(&app), 1);
+
+        // Gamepad navigation focuses widgets, which makes Egui want keyboard input.
+        app.world_mut()
+            .get_mut::<EguiContextWantsInput>(context)
+            .unwrap()
+            .0
+            .wants_keyboard_input = true;
+        write_input(&mut app);
+        app.update();
+        assert_eq!(message_counts(&app).2, 0);
+        assert!(!gamepad_pressed(&app));
+    }
+
+    #[test]
+    fn trackpad_gestures_are_forwarded() {
+        let mut app = App::new();
+        app.add_message::<RotationGesture>()
+            .add_message::<PanGesture>()
+            .add_message::<DoubleTapGesture>()
+            .add_message::<EguiInputEvent>()
+            .init_resource::<ModifierKeysState>()
+            .init_resource::<WindowModifierKeysState>()
+            .init_resource::<WindowToEguiContextMap>()
+            .add_systems(
+                Update,
+                (
+                    write_rotation_gesture_messages_system,
+                    write_pan_gesture_messages_system,
+                    write_double_tap_gesture_messages_system,
+                )
+                    .chain(),
+            );
+        let window = app.world_mut().spawn_empty().id();
+        let context = app.world_mut().spawn(EguiContext::default()).id();
+        app.world_mut()
+            .resource_mut::<WindowToEguiContextMap>()
+            .window_to_contexts
+            .entry(window)
+            .or_default()
+            .insert(context);
+        app.world_mut()
+            .get_mut::<EguiContextPointerPosition>(context)
+            .unwrap()
+            .position = egui::pos2(10.0, 20.0);
+
+        app.world_mut().write_message(RotationGesture(90.0));
+        app.world_mut()
+            .write_message(PanGesture(Vec2::new(3.0, -4.0)));
+        app.world_mut().write_message(DoubleTapGesture);
+        app.update();
+
+        let events = app
+            .world()
+            .resource::<Messages<EguiInputEvent>>()
+            .iter_current_update_messages()
+            .map(|message| {
+                assert_eq!(message.context, context);
+                message.event.clone()
+            })
+            .collect::<Vec<_>>();
+        let click = |pressed| egui::Event::PointerButton {
+            pos: egui::pos2(10.0, 20.0),
+            button: egui::PointerButton::Primary,
+            pressed,
+            modifiers: egui::Modifiers::NONE,
+        };
+        assert_eq!(
+            events,
+            [
+                egui::Event::Rotate(-std::f32::consts::FRAC_PI_2),
+                egui::Event::MouseWheel {
+                    unit: egui::MouseWheelUnit::Point,
+                    delta: egui::vec2(3.0, -4.0),
+                    phase: TouchPhase::Move,
+                    modifiers: egui::Modifiers::NONE,
+                },
+                click(true),
+                click(false),
+            ]
+        );
+
+        // The first tap of the double tap is delivered as a regular click.
+        let ctx = egui::Context::default();
+        let mut output = ctx.run_ui(
+            egui::RawInput {
+                events: [click(true), click(false)]
+                    .into_iter()
+                    .chain(events)
+                    .collect(),
+                ..Default::default()
+            },
+            |ui| {
+                assert_eq!(
+                    ui.input(|i| i.rotation_delta()),
+                    -std::f32::consts::FRAC_PI_2
+                );
+                assert!(ui.input(|i| {
+                    i.pointer
+                        .button_double_clicked(egui::PointerButton::Primary)
+                }));
+            },
+        );
+        output.textures_delta.clear();
+    }
+
+    #[test]
+    fn two_finger_touch_scrolls_scroll_area() {
+        use bevy_input::touch::TouchPhase;
+
+        let mut app = App::new();
+        app.add_message::<TouchInput>()
+            .add_message::<EguiInputEvent>()
+            .init_resource::<EguiGlobalSettings>()
+            .init_resource::<ModifierKeysState>()
+            .init_resource::<WindowModifierKeysState>()
+            .init_resource::<WindowToEguiContextMap>()
+            .init_resource::<Time<Real>>()
+            .add_systems(Update, write_window_touch_messages_system);
+        let window = app.world_mut().spawn_empty().id();
+        let context = app.world_mut().spawn(EguiContext::default()).id();
+        app.world_mut()
+            .resource_mut::<WindowToEguiContextMap>()
+            .window_to_contexts
+            .entry(window)
+            .or_default()
+            .insert(context);
+
+        let ctx = egui::Context::default();
+        let mut frame = 0;
+        let mut clicks = 0;
+        let mut scroll_offset = egui::Vec2::ZERO;
+        let mut button_rect = egui::Rect::NOTHING;
+        // Writes touch messages and runs a frame of both the app and the UI, returns the events sent to Egui.
+        let mut run = |app: &mut App, touches: &[(u64, TouchPhase, f32, f32)]| {
+            for &(id, phase, x, y) in touches {
+                app.world_mut().write_message(TouchInput {
+                    phase,
+                    position: Vec2::new(x, y),
+                    window,
+                    force: None,
+                    id,
+                });
+            }
+            app.update();
+            let events = app
+                .world()
+                .resource::<Messages<EguiInputEvent>>()
+                .iter_current_update_messages()
+                .map(|message| message.event.clone())
+                .collect::<Vec<_>>();
+            frame += 1;
+            let mut output = ctx.run_ui(
+                egui::RawInput {
+                    events: events.clone(),
+                    time: Some(frame as f64 / 60.0),
+                    screen_rect: Some(egui::Rect::from_min_size(
+                        egui::Pos2::ZERO,
+                        egui::vec2(200.0, 200.0),
+                    )),
+                    ..Default::default()
+                },
+                |ui| {
+                    let output = egui::ScrollArea::vertical().show(ui, |ui| {
+                        let response = ui.button("Button");
+                        if response.clicked() {
+                            clicks += 1;
+                        }
+                        button_rect = response.rect;
+                        ui.allocate_space(egui::vec2(100.0, 1000.0));
+                    });
+                    scroll_offset = output.state.offset;
+                },
+            );
+            output.textures_delta.clear();
+            (events, button_rect.center(), clicks, scroll_offset)
+        };
+
+        let (_, button, ..) = run(&mut app, &[]);
+        run(&mut app, &[(0, TouchPhase::Started, button.x, button.y)]);
+        run(
+            &mut app,
+            &[(1, TouchPhase::Started, button.x + 50.0, button.y)],
+        );
+        // Moving two fingers scrolls by the movement of their centroid, the emulated pointer stays in place.
+        let (events, ..) = run(
+            &mut app,
+            &[
+                (0, TouchPhase::Moved, button.x, button.y - 20.0),
+                (1, TouchPhase::Moved, button.x + 50.0, button.y - 40.0),
+            ],
+        );
+        let wheel_delta = events
+            .iter()
+            .map(|event| match event {
+                egui::Event::MouseWheel { delta, .. } => *delta,
+                egui::Event::PointerMoved(_) => panic!("the pointer must not move"),
+                _ => egui::Vec2::ZERO,
+            })
+            .fold(egui::Vec2::ZERO, |sum, delta| sum + delta);
+        assert_eq!(wheel_delta, egui::vec2(0.0, -30.0));
+        run(
+            &mut app,
+            &[(1, TouchPhase::Ended, button.x + 50.0, button.y - 40.0)],
+        );
+        run(
+            &mut app,
+            &[(0, TouchPhase::Ended, button.x, button.y - 20.0)],
+        );
+        let (_, _, clicks, scroll_offset) = run(&mut app, &[]);
+        // The touch that has started on the button isn't a click, as it has moved during scrolling.
+        assert_eq!(clicks, 0);
+        assert_eq!(scroll_offset, egui::vec2(0.0, 30.0));
+    }
+
+    #[test]
+    fn long_press_opens_context_menu() {
+        use bevy_input::touch::TouchPhase;
+        use std::time::Duration;
+
+        // Holds a touch on a button for 0.7 seconds (shorter than Egui's own long touch),
+        // returns the number of clicks and whether the context menu has opened.
+        fn long_press(window_context: bool) -> (u32, bool) {
+            let mut app = App::new();
+            app.add_message::<TouchInput>()
+                .add_message::<EguiInputEvent>()
+                .init_resource::<EguiGlobalSettings>()
+                .init_resource::<ModifierKeysState>()
+                .init_resource::<WindowModifierKeysState>()
+                .init_resource::<WindowToEguiContextMap>()
+                .init_resource::<Time<Real>>()
+                .add_systems(
+                    Update,
+                    (
+                        write_window_touch_messages_system,
+                        write_non_window_touch_messages_system,
+                        write_long_press_messages_system,
+                    )
+                        .chain(),
+                );
+            let window = app.world_mut().spawn_empty().id();
+            let context = app.world_mut().spawn(EguiContext::default()).id();
+            if window_context {
+                app.world_mut()
+                    .resource_mut::<WindowToEguiContextMap>()
+                    .window_to_contexts
+                    .entry(window)
+                    .or_default()
+                    .insert(context);
+            } else {
+                app.insert_resource(FocusedNonWindowEguiContext(context));
+            }
+            let mut time = app.world_mut().resource_mut::<Time<Real>>();
+            time.update_with_duration(Duration::ZERO);
+
+            let ctx = egui::Context::default();
+            let mut clicks = 0;
+            let mut menu_opened = false;
+            let mut button_position = egui::Pos2::ZERO;
+            let mut run = |app: &mut App, touch: Option<TouchPhase>| {
+                if let Some(phase) = touch {
+                    // Non-window contexts get touch positions from the pointer position (i.e. picking).
+                    app.world_mut()
+                        .get_mut::<EguiContextPointerPosition>(context)
+                        .unwrap()
+                        .position = button_position;
+                    app.world_mut().write_message(TouchInput {
+                        phase,
+                        position: Vec2::new(button_position.x, button_position.y),
+                        window,
+                        force: None,
+                        id: 0,
+                    });
+                }
+                app.world_mut()
+                    .resource_mut::<Time<Real>>()
+                    .update_with_duration(Duration::from_millis(100));
+                app.update();
+                let events = app
+                    .world()
+                    .resource::<Messages<EguiInputEvent>>()
+                    .iter_current_update_messages()
+                    .map(|message| message.event.clone())
+                    .collect();
+                let mut output = ctx.run_ui(
+                    egui::RawInput {
+                        events,
+                        time: Some(app.world().resource::<Time<Real>>().elapsed_secs_f64()),
+                        ..Default::default()
+                    },
+                    |ui| {
+                        let response = ui.button("Button");
+                        if response.clicked() {
+                            clicks += 1;
+                        }
+                        response.context_menu(|ui| {
+                            ui.label("Menu");
+                        });
+                        menu_opened |= response.context_menu_opened();
+                        button_position = response.rect.center();
+                    },
+                );
+                output.textures_delta.clear();
+            };
+
+            run(&mut app, None);
+            run(&mut app, Some(TouchPhase::Started));
+            for _ in 0..6 {
+                run(&mut app, None);
+            }
+            run(&mut app, Some(TouchPhase::Ended));
+            run(&mut app, None);
+            (clicks, menu_opened)
+        }
+
+        assert_eq!(long_press(true), (0, true));
+        assert_eq!(long_press(false), (0, true));
+    }
+
+    #[test]
+    fn pen_state_follows_pointer_touch() {
+        use bevy_input::touch::{ForceTouch, TouchPhase};
+
+        let mut app = App::new();
+        app.add_message::<TouchInput>()
+            .add_message::<EguiInputEvent>()
+            .init_resource::<EguiGlobalSettings>()
+            .init_resource::<ModifierKeysState>()
+            .init_resource::<WindowModifierKeysState>()
+            .init_resource::<WindowToEguiContextMap>()
+            .init_resource::<Time<Real>>()
+            .add_systems(
+                Update,
+                (write_window_touch_messages_system, write_pen_state_system).chain(),
+            );
+        let window = app.world_mut().spawn_empty().id();
+        let context = app.world_mut().spawn(EguiContext::default()).id();
+        app.world_mut()
+            .resource_mut::<WindowToEguiContextMap>()
+            .window_to_contexts
+            .entry(window)
+            .or_default()
+            .insert(context);
+        let touch = |id, phase, force| TouchInput {
+            phase,
+            position: Vec2::new(10.0, 10.0),
+            window,
+            force,
+            id,
+        };
+        let pen_state = |app: &mut App| {
+            let ctx = app
+                .world_mut()
+                .get_mut::<EguiContext>(context)
+                .unwrap()
+                .get_mut()
+                .clone();
+            let pen_state = EguiPenState::from_ctx(&ctx);
+            assert_eq!(
+                app.world().get::<EguiPenState>(context).unwrap(),
+                &pen_state
+            );
+            pen_state
+        };
+
+        app.world_mut().write_message(touch(
+            0,
+            TouchPhase::Started,
+            Some(ForceTouch::Calibrated {
+                force: 2.0,
+                max_possible_force: 4.0,
+                altitude_angle: Some(1.0),
+            }),
+        ));
+        // Other touches don't affect the state.
+        app.world_mut().write_message(touch(
+            1,
+            TouchPhase::Started,
+            Some(ForceTouch::Normalized(1.0)),
+        ));
+        app.update();
+        let state = pen_state(&mut app);
+        assert_eq!(
+            state,
+            EguiPenState {
+                touching: true,
+                pressure: Some(0.5),
+                altitude: Some(1.0),
+            }
+        );
+        assert!(state.is_pen());
+
+        app.world_mut()
+            .write_message(touch(0, TouchPhase::Ended, None));
+        app.update();
+        assert_eq!(pen_state(&mut app), EguiPenState::default());
+    }
+
+    #[test]
+    fn focusing_contexts_updates_raw_input_and_triggers_events() {
+        use crate::BevyEguiCommandsExt;
+
+        #[derive(Resource, Default)]
+        struct FocusEvents(Vec<(Entity, bool)>);
+
+        let mut app = App::new();
+        app.add_message::<EguiInputEvent>()
+            .add_message::<EguiFileDragAndDropMessage>()
+            .add_message::<WindowThemeChanged>()
+            .init_resource::<ModifierKeysState>()
+            .init_resource::<WindowModifierKeysState>()
+            .init_resource::<WindowToEguiContextMap>()
+            .init_resource::<Time<Real>>()
+            .init_resource::<FocusEvents>()
+            .add_observer(
+                |event: On<EguiContextFocused>, mut events: ResMut<FocusEvents>| {
+                    events.0.push((event.entity, true));
+                },
+            )
+            .add_observer(
+                |event: On<EguiContextUnfocused>, mut events: ResMut<FocusEvents>| {
+                    events.0.push((event.entity, false));
+                },
+            )
+            .add_systems(Update, write_egui_input_system);
+        let window = app.world_mut().spawn(Window::default()).id();
+        let window_context = app
+            .world_mut()
+            .spawn((EguiContext::default(), Camera::default()))
+            .id();
+        let image_context = app
+            .world_mut()
+            .spawn((EguiContext::default(), Camera::default()))
+            .id();
+        let mut map = app.world_mut().resource_mut::<WindowToEguiContextMap>();
+        map.window_to_contexts
+            .entry(window)
+            .or_default()
+            .insert(window_context);
+        map.context_to_window.insert(window_context, window);
+
+        let update = |app: &mut App| {
+            app.update();
+            let focused = |context| app.world().get::<EguiInput>(context).unwrap().focused;
+            let focused = (focused(window_context), focused(image_context));
+            let events = std::mem::take(&mut app.world_mut().resource_mut::<FocusEvents>().0);
+            (focused, events)
+        };
+        let focus = |app: &mut App, context| {
+            app.world_mut().commands().focus_egui_context(context);
+            app.world_mut().flush();
+        };
+
+        assert_eq!(
+            update(&mut app),
+            ((true, false), vec![(window_context, true)])
+        );
+
+        focus(&mut app, image_context);
+        assert_eq!(
+            update(&mut app),
+            (
+                (false, true),
+                vec![(window_context, false), (image_context, true)]
+            )
+        );
+
+        // Focusing a window context also focuses its window.
+        app.world_mut().get_mut::<Window>(window).unwrap().focused = false;
+        focus(&mut app, window_context);
+        assert_eq!(
+            update(&mut app),
+            (
+                (true, false),
+                vec![(window_context, true), (image_context, false)]
+            )
+        );
+        assert!(app.world().get::<Window>(window).unwrap().focused);
+
+        // Entities without a context are ignored.
+        let entity = app.world_mut().spawn_empty().id();
+        focus(&mut app, entity);
+        assert!(
+            !app.world()
+                .contains_resource::<FocusedNonWindowEguiContext>()
+        );
+        assert_eq!(update(&mut app), ((true, false), vec![]));
+    }
+}
+
+/// Reads both [`EguiFileDragAndDropMessage`] and [`EguiInputEvent`] messages and feeds them to Egui.
+///
+/// Also writes [`egui::RawInput::system_theme`] based on [`WindowThemeChanged`] messages
+/// (or [`EguiContextSettings::system_theme_override`]).
+///
+/// Events of contexts with the [`EguiInputFilters`] component go through the filters first.
+///
+/// Triggers [`EguiContextFocused`] and [`EguiContextUnfocused`] when [`egui::RawInput::focused`] of a context changes.
+#[allow(clippy::too_many_arguments)]
+pub fn write_egui_input_system(
+    mut commands: Commands,
+    focused_non_window_egui_context: Option<Res<FocusedNonWindowEguiContext>>,
+    window_to_egui_context_map: Res<WindowToEguiContextMap>,
+    modifier_keys_state: Res<ModifierKeysState>,
+    window_modifier_keys_state: Res<WindowModifierKeysState>,
+    mut egui_input_reader: MessageReader<EguiInputEvent>,
+    mut egui_file_dnd_message_reader: MessageReader<EguiFileDragAndDropMessage>,
+    mut window_theme_changed_reader: MessageReader<WindowThemeChanged>,
+    mut window_themes: Local<HashMap<Entity, WindowTheme>>,
+    mut focused_contexts: Local<HashSet<Entity>>,
+    mut egui_contexts: Query<(
+        Entity,
+        &mut EguiInput,
+        &EguiContextSettings,
+        &Camera,
+        Option<&mut EguiInputFilters>,
+    )>,
+    windows: Query<&Window>,
+    time: Res<Time<Real>>,
+) {
+    // Events that haven't been taken by the previous frame have already been filtered.
+    let filtered_event_counts: HashMap<Entity, usize> = egui_contexts
+        .iter()
+        .filter(|(.., filters)| filters.is_some())
+        .map(|(entity, egui_input, ..)| (entity, egui_input.events.len()))
+        .collect();
+
+    for EguiInputEvent { context, event, .. } in egui_input_reader.read() {
+        #[cfg(feature = "log_input_messages")]
+        log::warn!("{context:?}: {event:?}");
+
+        let (_entity, mut egui_input, ..) = match egui_contexts.get_mut(*context) {
+            Ok(egui_input) => egui_input,
+            Err(err) => {
+                log::error!(
+                    "Failed to get an Egui context ({context:?}) for an event ({event:?}): {err:?}"
+                );
+                continue;
+            }
+        };
+
+        egui_input.events.push(event.clone());
+    }
+
+    for EguiFileDragAndDropMessage { context, message } in egui_file_dnd_message_reader.read() {
+        #[cfg(feature = "log_file_dnd_messages")]
+        log::warn!("{context:?}: {message:?}");
+
+        let (_entity, mut egui_input, ..) = match egui_contexts.get_mut(*context) {
+            Ok(egui_input) => egui_input,
+            Err(err) => {
+                log::error!(
+                    "Failed to get an Egui context ({context:?}) for an message ({message:?}): {err:?}"
+                );
+                continue;
+            }
+        };
+
+        match message {
+            FileDragAndDrop::DroppedFile {
+                window: _,
+                path_buf,
+            } => {
+                egui_input.hovered_files.clear();
                 egui_input
                     .dropped_files
                     .push(std::sync::Arc::new(BevyDroppedFile {
@@ -1498,34 +4111,84 @@ pub fn write_egui_input_system(
         }
     }
 
-    for (entity, mut egui_input, camera) in egui_contexts.iter_mut() {
+    for WindowThemeChanged { window, theme } in window_theme_changed_reader.read() {
+        window_themes.insert(*window, *theme);
+    }
+
+    for (entity, mut egui_input, settings, camera, filters) in egui_contexts.iter_mut() {
+        let window_entity = window_to_egui_context_map
+            .context_to_window
+            .get(&entity)
+            .copied();
+        let window = window_entity.and_then(|window_entity| windows.get(window_entity).ok());
         egui_input.focused = focused_non_window_egui_context.as_deref().map_or_else(
-            || {
-                window_to_egui_context_map
-                    .context_to_window
-                    .get(&entity)
-                    .and_then(|window_entity| windows.get(*window_entity).ok())
-                    .is_some_and(|window| window.focused)
-            },
+            || window.is_some_and(|window| window.focused),
             |context| context.0 == entity,
         );
+        if egui_input.focused && focused_contexts.insert(entity) {
+            commands.trigger(EguiContextFocused { entity });
+        } else if !egui_input.focused && focused_contexts.remove(&entity) {
+            commands.trigger(EguiContextUnfocused { entity });
+        }
+        // Bevy updates `Window::window_theme` only on the window creation,
+        // the latest `WindowThemeChanged` message takes priority.
+        let system_theme = settings.system_theme_override.or_else(|| {
+            window_entity
+                .and_then(|window_entity| window_themes.get(&window_entity).copied())
+                .or_else(|| window.and_then(|window| window.window_theme))
+        });
+        egui_input.system_theme = system_theme.map(bevy_to_egui_theme);
+        let viewport_id = egui_input.viewport_id;
         egui_input
             .viewports
-            .entry(ViewportId::ROOT)
+            .entry(viewport_id)
             .or_default()
             .native_pixels_per_point = camera.target_scaling_factor();
-        egui_input.events.push(egui::Event::ModifiersChanged(
-            modifier_keys_state.to_egui_modifiers(),
-        ));
+        let modifiers = match window_entity {
+            Some(window_entity) => window_modifier_keys_state.get(window_entity),
+            None => &modifier_keys_state,
+        };
+        egui_input
+            .events
+            .push(egui::Event::ModifiersChanged(modifiers.to_egui_modifiers()));
+        if let Some(mut filters) = filters {
+            let filtered_event_count = filtered_event_counts.get(&entity).copied().unwrap_or(0);
+            let mut events = egui_input.events.split_off(filtered_event_count);
+            for filter in &mut filters.0 {
+                filter.filter(&mut events);
+            }
+            egui_input.events.extend(events);
+        }
         egui_input.time = Some(time.elapsed_secs_f64());
     }
+    // Forget despawned contexts and windows.
+    focused_contexts.retain(|&entity| egui_contexts.contains(entity));
+    window_themes.retain(|&window_entity, _| windows.contains(window_entity));
 }
 
-/// Clears Bevy input message buffers and resets [`ButtonInput`] resources if Egui
+/// Resets Bevy input resources and clears window-independent input messages if Egui
 /// is using pointer or keyboard (see the [`write_egui_wants_input_system`] run condition).
 ///
 /// This system isn't run by default, set [`EguiGlobalSettings::enable_absorb_bevy_input_system`]
-/// to `true` to enable it.
+/// to `true` to enable it. The absorbed input sources can be configured with [`EguiGlobalSettings::absorb_input_settings`].
+///
+/// Input is absorbed per window: pointer input is cleared if a context of the hovered window wants it,
+/// keyboard input is cleared if a context of the focused window wants it (see [`EguiContextWantsInput`]).
+/// Touch input and gestures are also cleared if a context of the focused window wants pointer input,
+/// as touch screens don't update the cursor position.
+/// Contexts that aren't attached to a window (rendered to images, for example) count for every window,
+/// as they can receive input from any of them.
+///
+/// Gamepad input is cleared if the focused window wants keyboard input (gamepad navigation moves keyboard focus),
+/// or a context with [`crate::virtual_cursor::EguiGamepadVirtualCursor`] wants pointer input.
+/// Stick positions stored in [`Gamepad`] components are kept, as Bevy updates them only when they change.
+///
+/// [`KeyboardInput`], [`MouseButtonInput`](bevy_input::mouse::MouseButtonInput), [`MouseWheel`] and [`TouchInput`] messages are never cleared,
+/// as they belong to windows, and other windows may still need them. Only the resources Bevy derives from them
+/// ([`ButtonInput`], [`AccumulatedMouseScroll`] and [`Touches`]) are reset. Systems that read these messages
+/// with a [`MessageReader`] have to skip the messages of the windows that Egui is using themselves,
+/// for example, with [`egui_wants_pointer_input_for`] and [`egui_wants_keyboard_input_for`],
+/// or by checking [`EguiContextWantsInput`] of the contexts in [`WindowToEguiContextMap`].
 ///
 /// ## Considerations
 ///
@@ -1537,47 +4200,188 @@ pub fn write_egui_input_system(
 /// ## Alternative
 ///
 /// A safer alternative is to apply `run_if(not(egui_wants_any_pointer_input))` or `run_if(not(egui_wants_any_keyboard_input))` to your systems
-/// that need to be disabled while Egui is using input (see the [`egui_wants_any_pointer_input`], [`egui_wants_any_keyboard_input`] run conditions).
+/// that need to be disabled while Egui is using input (see the [`egui_wants_any_pointer_input`], [`egui_wants_any_keyboard_input`] run conditions,
+/// and [`egui_wants_pointer_input_for`], [`egui_wants_keyboard_input_for`] for the per-window ones).
 pub fn absorb_bevy_input_system(
-    egui_wants_input: Res<EguiWantsInput>,
-    mut mouse_input: ResMut<ButtonInput<MouseButton>>,
-    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
-    mut keyboard_input_messages: ResMut<Messages<KeyboardInput>>,
-    mut mouse_wheel_messages: ResMut<Messages<MouseWheel>>,
-    mut mouse_button_input_messages: ResMut<Messages<MouseButtonInput>>,
+    egui_global_settings: Res<EguiGlobalSettings>,
+    window_to_egui_context_map: Res<WindowToEguiContextMap>,
+    context_wants_input: Query<(Entity, &EguiContextWantsInput)>,
+    virtual_cursors: Query<&EguiContextWantsInput, With<EguiGamepadVirtualCursor>>,
+    windows: Query<(Entity, &Window)>,
+    mut input: AbsorbableBevyInput,
 ) {
-    let modifiers = [
-        KeyCode::SuperLeft,
-        KeyCode::SuperRight,
-        KeyCode::ControlLeft,
-        KeyCode::ControlRight,
-        KeyCode::AltLeft,
-        KeyCode::AltRight,
-        KeyCode::ShiftLeft,
-        KeyCode::ShiftRight,
-    ];
+    let settings = &egui_global_settings.absorb_input_settings;
+    let wants_pointer_input = |window: Entity| {
+        window_wants_input(
+            window,
+            &window_to_egui_context_map,
+            &context_wants_input,
+            EguiWantsInput::wants_any_pointer_input,
+        )
+    };
+    let wants_keyboard_input = |window: Entity| {
+        window_wants_input(
+            window,
+            &window_to_egui_context_map,
+            &context_wants_input,
+            EguiWantsInput::wants_any_keyboard_input,
+        )
+    };
+    let hovered_window_wants_pointer_input = windows.iter().any(|(window_entity, window)| {
+        window.cursor_position().is_some() && wants_pointer_input(window_entity)
+    });
+    let focused_window_wants_pointer_input = windows
+        .iter()
+        .any(|(window_entity, window)| window.focused && wants_pointer_input(window_entity));
+    let focused_window_wants_keyboard_input = windows
+        .iter()
+        .any(|(window_entity, window)| window.focused && wants_keyboard_input(window_entity));
+
+    if settings.absorb_keyboard && focused_window_wants_keyboard_input {
+        reset_button_input_except(
+            &mut input.keyboard_input,
+            &[
+                KeyCode::SuperLeft,
+                KeyCode::SuperRight,
+                KeyCode::ControlLeft,
+                KeyCode::ControlRight,
+                KeyCode::AltLeft,
+                KeyCode::AltRight,
+                KeyCode::ShiftLeft,
+                KeyCode::ShiftRight,
+            ],
+        );
+        if let Some(key_input) = &mut input.key_input {
+            reset_button_input_except(
+                key_input,
+                &[
+                    Key::Super,
+                    Key::Meta,
+                    Key::Control,
+                    Key::Alt,
+                    Key::AltGraph,
+                    Key::Shift,
+                ],
+            );
+        }
+    }
+
+    if settings.absorb_mouse_buttons && hovered_window_wants_pointer_input {
+        input.mouse_input.reset_all();
+    }
+
+    if settings.absorb_mouse_wheel
+        && hovered_window_wants_pointer_input
+        && let Some(accumulated_mouse_scroll) = &mut input.accumulated_mouse_scroll
+    {
+        accumulated_mouse_scroll.delta = Vec2::ZERO;
+    }
+
+    if settings.absorb_mouse_motion && hovered_window_wants_pointer_input {
+        if let Some(mouse_motion_messages) = &mut input.mouse_motion_messages {
+            mouse_motion_messages.clear();
+        }
+        if let Some(accumulated_mouse_motion) = &mut input.accumulated_mouse_motion {
+            accumulated_mouse_motion.delta = Vec2::ZERO;
+        }
+    }
+
+    if settings.absorb_touch
+        && focused_window_wants_pointer_input
+        && let Some(touches) = &mut input.touches
+    {
+        touches.reset_all();
+    }
 
-    let pressed = modifiers.map(|key| keyboard_input.pressed(key).then_some(key));
+    if settings.absorb_gestures
+        && (hovered_window_wants_pointer_input || focused_window_wants_pointer_input)
+    {
+        clear_messages(&mut input.pinch_gesture_messages);
+        clear_messages(&mut input.rotation_gesture_messages);
+        clear_messages(&mut input.double_tap_gesture_messages);
+        clear_messages(&mut input.pan_gesture_messages);
+    }
 
-    // TODO: the list of messages is definitely not comprehensive, but it should at least cover
-    //  the most popular use-cases. We can add more on request.
-    if egui_wants_input.wants_any_keyboard_input() {
-        keyboard_input.reset_all();
-        keyboard_input_messages.clear();
+    if settings.absorb_gamepad
+        && (focused_window_wants_keyboard_input
+            || virtual_cursors
+                .iter()
+                .any(|wants_input| wants_input.wants_any_pointer_input()))
+    {
+        clear_messages(&mut input.gamepad_button_state_changed_messages);
+        clear_messages(&mut input.gamepad_button_changed_messages);
+        clear_messages(&mut input.gamepad_axis_changed_messages);
+        for mut gamepad in &mut input.gamepads {
+            gamepad.digital_mut().reset_all();
+        }
     }
-    if egui_wants_input.wants_any_pointer_input() {
-        mouse_input.reset_all();
-        mouse_wheel_messages.clear();
-        mouse_button_input_messages.clear();
+}
+
+/// Input resources and messages that [`absorb_bevy_input_system`] clears.
+///
+/// Resources and messages of the `bevy_input` features that may be disabled are optional.
+#[derive(SystemParam)]
+pub struct AbsorbableBevyInput<'w, 's> {
+    mouse_input: ResMut<'w, ButtonInput<MouseButton>>,
+    keyboard_input: ResMut<'w, ButtonInput<KeyCode>>,
+    key_input: Option<ResMut<'w, ButtonInput<Key>>>,
+    mouse_motion_messages: Option<ResMut<'w, Messages<MouseMotion>>>,
+    accumulated_mouse_scroll: Option<ResMut<'w, AccumulatedMouseScroll>>,
+    accumulated_mouse_motion: Option<ResMut<'w, AccumulatedMouseMotion>>,
+    touches: Option<ResMut<'w, Touches>>,
+    pinch_gesture_messages: Option<ResMut<'w, Messages<PinchGesture>>>,
+    rotation_gesture_messages: Option<ResMut<'w, Messages<RotationGesture>>>,
+    double_tap_gesture_messages: Option<ResMut<'w, Messages<DoubleTapGesture>>>,
+    pan_gesture_messages: Option<ResMut<'w, Messages<PanGesture>>>,
+    gamepad_button_state_changed_messages:
+        Option<ResMut<'w, Messages<GamepadButtonStateChangedEvent>>>,
+    gamepad_button_changed_messages: Option<ResMut<'w, Messages<GamepadButtonChangedEvent>>>,
+    gamepad_axis_changed_messages: Option<ResMut<'w, Messages<GamepadAxisChangedEvent>>>,
+    gamepads: Query<'w, 's, &'static mut Gamepad>,
+}
+
+/// Resets the input, keeping the `kept` buttons (such as modifier keys) pressed if they are held.
+fn reset_button_input_except<T>(input: &mut ButtonInput<T>, kept: &[T])
+where
+    T: Clone + Eq + std::hash::Hash + Send + Sync + 'static,
+{
+    let pressed = kept
+        .iter()
+        .filter(|button| input.pressed((*button).clone()))
+        .cloned()
+        .collect::<Vec<_>>();
+    input.reset_all();
+    for button in pressed {
+        input.press(button);
     }
+}
 
-    for key in pressed.into_iter().flatten() {
-        keyboard_input.press(key);
+fn clear_messages<M: Message>(messages: &mut Option<ResMut<Messages<M>>>) {
+    if let Some(messages) = messages {
+        messages.clear();
     }
 }
 
+/// Checks the contexts of the window, and the contexts that aren't attached to any window.
+fn window_wants_input(
+    window: Entity,
+    window_to_egui_context_map: &WindowToEguiContextMap,
+    context_wants_input: &Query<(Entity, &EguiContextWantsInput)>,
+    test: impl Fn(&EguiWantsInput) -> bool,
+) -> bool {
+    context_wants_input.iter().any(|(context, wants_input)| {
+        window_to_egui_context_map
+            .context_to_window
+            .get(&context)
+            .is_none_or(|context_window| *context_window == window)
+            && test(wants_input)
+    })
+}
+
 /// Stores whether there's an Egui context using pointer or keyboard.
-#[derive(Resource, Clone, Debug, Default)]
+///
+/// The state of each context is also available via the [`EguiContextWantsInput`] component.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
 pub struct EguiWantsInput {
     is_pointer_over_area: bool,
     wants_pointer_input: bool,
@@ -1653,27 +4457,42 @@ impl EguiWantsInput {
         self.wants_keyboard_input = false;
         self.is_popup_open = false;
     }
+
+    fn merge(&mut self, other: &Self) {
+        self.is_pointer_over_area = self.is_pointer_over_area || other.is_pointer_over_area;
+        self.wants_pointer_input = self.wants_pointer_input || other.wants_pointer_input;
+        self.is_using_pointer = self.is_using_pointer || other.is_using_pointer;
+        self.wants_keyboard_input = self.wants_keyboard_input || other.wants_keyboard_input;
+        self.is_popup_open = self.is_popup_open || other.is_popup_open;
+    }
 }
 
-/// Updates the [`EguiWantsInput`] resource.
+/// Stores whether an Egui context is using pointer or keyboard.
+///
+/// Unlike the [`EguiWantsInput`] resource, which combines all contexts, this component lets
+/// multi-window apps check the input of a single context (see also [`egui_wants_pointer_input_for`]
+/// and [`egui_wants_keyboard_input_for`]).
+#[derive(Component, Clone, Debug, Default, PartialEq, Deref)]
+pub struct EguiContextWantsInput(EguiWantsInput);
+
+/// Updates the [`EguiWantsInput`] resource and the [`EguiContextWantsInput`] components.
 pub fn write_egui_wants_input_system(
-    mut egui_context_query: Query<&mut EguiContext>,
+    mut egui_context_query: Query<(&mut EguiContext, &mut EguiContextWantsInput)>,
     mut egui_wants_input: ResMut<EguiWantsInput>,
 ) {
     egui_wants_input.reset();
 
-    for mut ctx in egui_context_query.iter_mut() {
+    for (mut ctx, mut context_wants_input) in egui_context_query.iter_mut() {
         let egui_ctx = ctx.get_mut();
-        egui_wants_input.is_pointer_over_area =
-            egui_wants_input.is_pointer_over_area || egui_ctx.is_pointer_over_egui();
-        egui_wants_input.wants_pointer_input =
-            egui_wants_input.wants_pointer_input || egui_ctx.egui_wants_pointer_input();
-        egui_wants_input.is_using_pointer =
-            egui_wants_input.is_using_pointer || egui_ctx.egui_is_using_pointer();
-        egui_wants_input.wants_keyboard_input =
-            egui_wants_input.wants_keyboard_input || egui_ctx.egui_wants_keyboard_input();
-        egui_wants_input.is_popup_open =
-            egui_wants_input.is_popup_open || egui_ctx.any_popup_open();
+        let wants_input = EguiWantsInput {
+            is_pointer_over_area: egui_ctx.is_pointer_over_egui(),
+            wants_pointer_input: egui_ctx.egui_wants_pointer_input(),
+            is_using_pointer: egui_ctx.egui_is_using_pointer(),
+            wants_keyboard_input: egui_ctx.egui_wants_keyboard_input(),
+            is_popup_open: egui_ctx.any_popup_open(),
+        };
+        egui_wants_input.merge(&wants_input);
+        context_wants_input.set_if_neq(EguiContextWantsInput(wants_input));
     }
 }
 
@@ -1694,3 +4513,54 @@ pub fn egui_wants_any_keyboard_input(egui_wants_input_resource: Res<EguiWantsInp
 pub fn egui_wants_any_input(egui_wants_input_resource: Res<EguiWantsInput>) -> bool {
     egui_wants_input_resource.wants_any_input()
 }
+
+/// Returns a run condition that is `true` if an Egui context of the window wants pointer input
+/// (see [`EguiWantsInput::wants_any_pointer_input`]).
+///
+/// Contexts that aren't attached to a window (rendered to images, for example) count for every window.
+pub fn egui_wants_pointer_input_for(
+    window: Entity,
+) -> impl Fn(Res<WindowToEguiContextMap>, Query<(Entity, &EguiContextWantsInput)>) -> bool + Clone {
+    move |window_to_egui_context_map, context_wants_input| {
+        window_wants_input(
+            window,
+            &window_to_egui_context_map,
+            &context_wants_input,
+            EguiWantsInput::wants_any_pointer_input,
+        )
+    }
+}
+
+/// Returns a run condition that is `true` if an Egui context of the window wants keyboard input
+/// (see [`EguiWantsInput::wants_any_keyboard_input`]).
+///
+/// Contexts that aren't attached to a window (rendered to images, for example) count for every window.
+pub fn egui_wants_keyboard_input_for(
+    window: Entity,
+) -> impl Fn(Res<WindowToEguiContextMap>, Query<(Entity, &EguiContextWantsInput)>) -> bool + Clone {
+    move |window_to_egui_context_map, context_wants_input| {
+        window_wants_input(
+            window,
+            &window_to_egui_context_map,
+            &context_wants_input,
+            EguiWantsInput::wants_any_keyboard_input,
+        )
+    }
+}
+
+/// Returns a run condition that is `true` if an Egui context of the window wants any input
+/// (see [`EguiWantsInput::wants_any_input`]).
+///
+/// Contexts that aren't attached to a window (rendered to images, for example) count for every window.
+pub fn egui_wants_input_for(
+    window: Entity,
+) -> impl Fn(Res<WindowToEguiContextMap>, Query<(Entity, &EguiContextWantsInput)>) -> bool + Clone {
+    move |window_to_egui_context_map, context_wants_input| {
+        window_wants_input(
+            window,
+            &window_to_egui_context_map,
+            &context_wants_input,
+            EguiWantsInput::wants_any_input,
+        )
+    }
+}
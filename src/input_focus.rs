@@ -0,0 +1,343 @@
+use crate::{EguiContext, EguiInput, input::ModifierKeysState};
+use bevy_ecs::prelude::*;
+use bevy_input::{
+    ButtonState,
+    keyboard::{KeyCode, KeyboardInput},
+};
+use bevy_input_focus::{
+    FocusCause, FocusedInput, InputFocus,
+    tab_navigation::{NavAction, TabIndex, TabNavigation},
+};
+
+/// Keyboard focus state of an Egui context, synced with [`InputFocus`] by [`write_input_focus_system`].
+///
+/// While a widget of a context (e.g. a text field) has Egui keyboard focus, [`InputFocus`] points at the context entity,
+/// so bevy_input_focus emits [`bevy_input_focus::FocusGained`] and [`bevy_input_focus::FocusLost`] events
+/// for the context when the focus moves in or out of it.
+/// When [`InputFocus`] moves to another entity, the context surrenders its Egui keyboard focus.
+///
+/// Add [`TabIndex`] to a context entity (and make it a descendant of a [`bevy_input_focus::tab_navigation::TabGroup`])
+/// to make the context a tab stop: tabbing into the context focuses its first (or last, with Shift+Tab) widget,
+/// and tabbing out of its last (or first) widget moves [`InputFocus`] to the next (or previous) tab stop.
+#[derive(Component, Clone, Debug, Default)]
+pub struct EguiInputFocusState {
+    /// The widget that had Egui keyboard focus when the state was last synced.
+    pub focused_widget: Option<egui::Id>,
+    /// The first focusable widget of the context, learned when Egui focus enters it with Tab.
+    /// Shift+Tab on this widget moves [`InputFocus`] to the previous tab stop.
+    pub first_widget: Option<egui::Id>,
+    /// Egui wraps focus around on the next pass after tabbing out of the last widget, the widget is surrendered instead.
+    surrender_next: bool,
+    /// Tab navigation to replay once the context receives [`InputFocus`] from another context.
+    enter: Option<NavAction>,
+}
+
+/// Stops [`FocusedInput`] Tab key presses from propagating to [`bevy_input_focus::tab_navigation::handle_tab_navigation`]
+/// while an Egui context has keyboard focus, so that Egui moves focus between its own widgets.
+///
+/// Shift+Tab on the first widget of a context that is a tab stop is let through.
+pub fn stop_egui_tab_navigation_observer(
+    mut event: On<FocusedInput<KeyboardInput>>,
+    mut egui_contexts: Query<(&mut EguiContext, &EguiInputFocusState, Has<TabIndex>)>,
+    modifier_keys_state: Res<ModifierKeysState>,
+) {
+    let input = &event.input;
+    if input.key_code != KeyCode::Tab || input.state != ButtonState::Pressed {
+        return;
+    }
+    let Ok((mut ctx, state, is_tab_stop)) = egui_contexts.get_mut(event.focused_entity) else {
+        return;
+    };
+    let Some(focused_widget) = ctx.get_mut().memory(|memory| memory.focused()) else {
+        return;
+    };
+
+    if is_tab_stop && modifier_keys_state.shift && state.first_widget == Some(focused_widget) {
+        return;
+    }
+    event.propagate(false);
+}
+
+/// Removes Tab key presses from the input of contexts that are tab stops but don't have [`InputFocus`],
+/// and replays Tab navigation for contexts that received [`InputFocus`] from another context.
+pub fn write_tab_navigation_input_system(
+    input_focus: Res<InputFocus>,
+    mut egui_contexts: Query<
+        (Entity, &mut EguiInput, &mut EguiInputFocusState),
+        (With<EguiContext>, With<TabIndex>),
+    >,
+) {
+    for (context, mut egui_input, mut state) in egui_contexts.iter_mut() {
+        let enter = state.enter.take();
+        if input_focus.get() != Some(context) {
+            egui_input.events.retain(|event| {
+                !matches!(
+                    event,
+                    egui::Event::Key {
+                        key: egui::Key::Tab,
+                        ..
+                    }
+                )
+            });
+            continue;
+        }
+
+        if let Some(action) = enter {
+            egui_input.events.push(egui::Event::Key {
+                key: egui::Key::Tab,
+                physical_key: Some(egui::Key::Tab),
+                pressed: true,
+                repeat: false,
+                modifiers: if action == NavAction::Previous {
+                    egui::Modifiers::SHIFT
+                } else {
+                    egui::Modifiers::NONE
+                },
+            });
+        }
+    }
+}
+
+/// Syncs Egui keyboard focus of contexts with [`InputFocus`] (see [`EguiInputFocusState`]).
+pub fn write_input_focus_system(
+    mut input_focus: ResMut<InputFocus>,
+    tab_navigation: TabNavigation,
+    mut egui_contexts: Query<(
+        Entity,
+        &mut EguiContext,
+        &mut EguiInputFocusState,
+        Has<TabIndex>,
+    )>,
+) {
+    let mut entered_context = None;
+    for (context, mut ctx, mut state, is_tab_stop) in egui_contexts.iter_mut() {
+        let ctx = ctx.get_mut();
+        let focused_widget = ctx.memory(|memory| memory.focused());
+        let (tab_action, pointer_pressed) = ctx.input(|input| {
+            (
+                tab_navigation_action(&input.events),
+                input.pointer.any_pressed(),
+            )
+        });
+        let previous_widget = std::mem::replace(&mut state.focused_widget, focused_widget);
+        let surrender_next = std::mem::take(&mut state.surrender_next);
+        let has_input_focus = input_focus.get() == Some(context);
+
+        match focused_widget {
+            Some(widget) if previous_widget != Some(widget) => {
+                if surrender_next
+                    || previous_widget.is_none() && tab_action == Some(NavAction::Next)
+                {
+                    state.first_widget = Some(widget);
+                }
+
+                if has_input_focus {
+                    continue;
+                }
+                if surrender_next {
+                    // Egui wraps focus around after tabbing out of the last widget,
+                    // but `InputFocus` has already moved to the next tab stop.
+                    ctx.memory_mut(|memory| memory.surrender_focus(widget));
+                    state.focused_widget = None;
+                } else {
+                    let cause = if pointer_pressed {
+                        FocusCause::Pressed
+                    } else {
+                        FocusCause::Navigated
+                    };
+                    input_focus.set(context, cause);
+                }
+            }
+            Some(widget) if !has_input_focus => {
+                // `InputFocus` has moved to another entity.
+                ctx.memory_mut(|memory| memory.surrender_focus(widget));
+                state.focused_widget = None;
+            }
+            None if previous_widget.is_some() && has_input_focus => {
+                if tab_action != Some(NavAction::Next) {
+                    input_focus.clear();
+                } else if is_tab_stop
+                    && let Ok(next) = tab_navigation.navigate(&input_focus, NavAction::Next)
+                    && next != context
+                {
+                    // Tabbing out of the last widget.
+                    input_focus.set(next, FocusCause::Navigated);
+                    state.surrender_next = true;
+                    entered_context = Some(next);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(next) = entered_context
+        && let Ok((_, _, mut state, _)) = egui_contexts.get_mut(next)
+    {
+        state.enter = Some(NavAction::Next);
+    }
+}
+
+fn tab_navigation_action(events: &[egui::Event]) -> Option<NavAction> {
+    events.iter().rev().find_map(|event| match event {
+        egui::Event::Key {
+            key: egui::Key::Tab,
+            pressed: true,
+            modifiers,
+            ..
+        } => {
+            if modifiers.is_none() {
+                Some(NavAction::Next)
+            } else if modifiers.shift_only() {
+                Some(NavAction::Previous)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::{App, Update};
+    use bevy_input::{ButtonInput, keyboard::Key};
+    use bevy_input_focus::{
+        InputFocusVisible, dispatch_focused_input,
+        tab_navigation::{TabGroup, handle_tab_navigation},
+    };
+    use bevy_window::{PrimaryWindow, Window};
+
+    fn run_pass_system(mut egui_contexts: Query<(&mut EguiContext, &mut EguiInput)>) {
+        for (mut ctx, mut egui_input) in egui_contexts.iter_mut() {
+            let mut output = ctx.get_mut().run_ui(egui_input.take(), |ui| {
+                ui.text_edit_singleline(&mut String::new());
+                ui.text_edit_singleline(&mut String::new());
+            });
+            output.textures_delta.clear();
+        }
+    }
+
+    fn focused_widget(app: &mut App, context: Entity) -> Option<egui::Id> {
+        app.world_mut()
+            .get_mut::<EguiContext>(context)
+            .unwrap()
+            .get_mut()
+            .memory(|memory| memory.focused())
+    }
+
+    fn press(app: &mut App, window: Entity, context: Entity, key: egui::Key, shift: bool) {
+        let world = app.world_mut();
+        world.resource_mut::<ModifierKeysState>().shift = shift;
+        let mut keys = world.resource_mut::<ButtonInput<KeyCode>>();
+        keys.reset_all();
+        if shift {
+            keys.press(KeyCode::ShiftLeft);
+        }
+        let (key_code, logical_key) = match key {
+            egui::Key::Tab => (KeyCode::Tab, Key::Tab),
+            _ => (KeyCode::Escape, Key::Escape),
+        };
+        world.write_message(KeyboardInput {
+            key_code,
+            logical_key,
+            state: ButtonState::Pressed,
+            text: None,
+            repeat: false,
+            window,
+        });
+        world
+            .get_mut::<EguiInput>(context)
+            .unwrap()
+            .events
+            .push(egui::Event::Key {
+                key,
+                physical_key: Some(key),
+                pressed: true,
+                repeat: false,
+                modifiers: if shift {
+                    egui::Modifiers::SHIFT
+                } else {
+                    egui::Modifiers::NONE
+                },
+            });
+        app.update();
+    }
+
+    #[test]
+    fn tab_navigation_moves_focus_between_egui_and_bevy() {
+        let mut app = App::new();
+        app.add_message::<KeyboardInput>()
+            .init_resource::<InputFocus>()
+            .init_resource::<InputFocusVisible>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ModifierKeysState>()
+            .add_observer(stop_egui_tab_navigation_observer)
+            .add_systems(
+                Update,
+                (
+                    dispatch_focused_input::<KeyboardInput>,
+                    write_tab_navigation_input_system,
+                    run_pass_system,
+                    write_input_focus_system,
+                )
+                    .chain(),
+            );
+        let world = app.world_mut();
+        let window = world.spawn((Window::default(), PrimaryWindow)).id();
+        world.entity_mut(window).observe(handle_tab_navigation);
+        let group = world.spawn(TabGroup::new(0)).id();
+        let button_a = world.spawn((TabIndex(0), ChildOf(group))).id();
+        let context = world
+            .spawn((EguiContext::default(), TabIndex(1), ChildOf(group)))
+            .id();
+        let button_b = world.spawn((TabIndex(2), ChildOf(group))).id();
+        world
+            .resource_mut::<InputFocus>()
+            .set(button_a, FocusCause::Navigated);
+        let input_focus = |app: &App| app.world().resource::<InputFocus>().get();
+
+        // Tabbing into the context focuses its first widget.
+        press(&mut app, window, context, egui::Key::Tab, false);
+        let first_widget = focused_widget(&mut app, context);
+        assert!(first_widget.is_some());
+        assert_eq!(input_focus(&app), Some(context));
+
+        // Tab moves focus between Egui widgets.
+        press(&mut app, window, context, egui::Key::Tab, false);
+        let last_widget = focused_widget(&mut app, context);
+        assert!(last_widget.is_some() && last_widget != first_widget);
+        assert_eq!(input_focus(&app), Some(context));
+
+        // Tabbing out of the last widget moves focus to the next tab stop.
+        press(&mut app, window, context, egui::Key::Tab, false);
+        assert_eq!(input_focus(&app), Some(button_b));
+        app.update();
+        assert_eq!(focused_widget(&mut app, context), None);
+        assert_eq!(input_focus(&app), Some(button_b));
+
+        // Shift+Tab into the context focuses its last widget, and out of the first widget moves focus back.
+        press(&mut app, window, context, egui::Key::Tab, true);
+        assert_eq!(focused_widget(&mut app, context), last_widget);
+        assert_eq!(input_focus(&app), Some(context));
+        press(&mut app, window, context, egui::Key::Tab, true);
+        app.update();
+        assert_eq!(focused_widget(&mut app, context), first_widget);
+        assert_eq!(input_focus(&app), Some(context));
+        press(&mut app, window, context, egui::Key::Tab, true);
+        assert_eq!(input_focus(&app), Some(button_a));
+        assert_eq!(focused_widget(&mut app, context), None);
+
+        // Focusing a widget in Egui moves `InputFocus` to the context, and Escape clears it.
+        app.world_mut()
+            .get_mut::<EguiContext>(context)
+            .unwrap()
+            .get_mut()
+            .memory_mut(|memory| memory.request_focus(first_widget.unwrap()));
+        app.update();
+        assert_eq!(input_focus(&app), Some(context));
+        press(&mut app, window, context, egui::Key::Escape, false);
+        assert_eq!(focused_widget(&mut app, context), None);
+        assert_eq!(input_focus(&app), None);
+    }
+}
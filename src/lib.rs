@@ -95,6 +95,10 @@
 //!
 //! Setting up two windows with an Egui context for each.
 //!
+//! ### multiple_viewports (source: [examples/multiple_viewports.rs](https://github.com/vladbat00/bevy_egui/blob/v0.42.0/examples/multiple_viewports.rs))
+//!
+//! Showing immediate and deferred Egui viewports as native windows.
+//!
 //! ### paint_callback ([live page](https://vladbat00.github.io/bevy_egui/paint_callback), source: [examples/paint_callback.rs](https://github.com/vladbat00/bevy_egui/blob/v0.42.0/examples/paint_callback.rs))
 //!
 //! Using Egui paint callbacks.
@@ -111,21 +115,37 @@
 //!
 //! - [`bevy-inspector-egui`](https://github.com/jakobhellermann/bevy-inspector-egui)
 
+/// Clipboard backends (see [`EguiClipboard`]).
+pub mod clipboard;
 /// Helpers for converting Bevy types into Egui ones and vice versa.
 pub mod helpers;
 /// Systems for translating Bevy input messages into Egui input.
 pub mod input;
+/// `bevy_input_focus` integration for Egui (requires the `input_focus` feature).
+#[cfg(feature = "input_focus")]
+pub mod input_focus;
 /// Systems for handling Egui output.
 pub mod output;
 /// `bevy_picking` integration for Egui.
 #[cfg(feature = "picking")]
 pub mod picking;
+/// Recording and replaying Egui input (requires the `input_recording` feature).
+#[cfg(feature = "input_recording")]
+pub mod recording;
 /// Rendering Egui with [`bevy_render`].
 #[cfg(feature = "render")]
 pub mod render;
+/// Keyboard shortcuts that emit Bevy messages (see [`shortcuts::EguiShortcuts`]).
+pub mod shortcuts;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 /// Mobile web keyboard input support.
 #[cfg(target_arch = "wasm32")]
 pub mod text_agent;
+/// Native Egui viewports support (see [`EguiContextSettings::enable_native_viewports`]).
+pub mod viewport;
+/// Gamepad-driven virtual cursor (see [`virtual_cursor::EguiGamepadVirtualCursor`]).
+pub mod virtual_cursor;
 /// Clipboard management for web.
 #[cfg(all(feature = "manage_clipboard", target_arch = "wasm32",))]
 pub mod web_clipboard;
@@ -142,6 +162,7 @@ pub mod prelude {
 
 pub use egui;
 
+use crate::clipboard::EguiClipboardBackend;
 use crate::input::*;
 #[cfg(target_arch = "wasm32")]
 use crate::text_agent::{
@@ -158,7 +179,7 @@ use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
     prelude::*,
     query::{QueryData, QueryEntityError, QuerySingleError},
-    schedule::{InternedScheduleLabel, ScheduleLabel},
+    schedule::{InternedScheduleLabel, ScheduleLabel, SingleThreadedExecutor},
     system::SystemParam,
 };
 #[cfg(feature = "render")]
@@ -181,23 +202,10 @@ use bevy_render::{
     extract_resource::{ExtractResource, ExtractResourcePlugin},
     render_resource::SpecializedRenderPipelines,
 };
-use output::process_output_system;
-#[cfg(all(
-    feature = "manage_clipboard",
-    not(any(target_arch = "wasm32", target_os = "android"))
-))]
-use std::cell::{RefCell, RefMut};
-#[cfg(all(
-    any(
-        target_os = "linux",
-        target_os = "dragonfly",
-        target_os = "freebsd",
-        target_os = "netbsd",
-        target_os = "openbsd"
-    ),
-    feature = "manage_clipboard"
-))]
-use std::sync::{Arc, Mutex};
+use output::{
+    EguiRepaintScheduler, process_output_system, process_viewport_commands_system,
+    schedule_repaints_system,
+};
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
 
@@ -274,6 +282,8 @@ pub struct EguiGlobalSettings {
     /// Apply `run_if(not(egui_wants_any_pointer_input))` or `run_if(not(egui_wants_any_keyboard_input))` to your systems
     /// that need to be disabled while Egui is using input (see the [`egui_wants_any_pointer_input`], [`egui_wants_any_keyboard_input`] run conditions).
     pub enable_absorb_bevy_input_system: bool,
+    /// Controls which input sources the [`absorb_bevy_input_system`] system absorbs.
+    pub absorb_input_settings: EguiAbsorbInputSettings,
     /// Controls whether `bevy_egui` updates [`bevy_window::CursorIcon`], enabled by default.
     ///
     /// If you want to have custom cursor icons in your app, set this to `false` to avoid Egui
@@ -281,6 +291,17 @@ pub struct EguiGlobalSettings {
     pub enable_cursor_icon_updates: bool,
     /// Controls whether the [`process_ime_system`] and [`write_ime_messages_system`] systems are run, enabled by default.
     pub enable_ime: bool,
+    /// Controls whether `bevy_egui` applies [`egui::ViewportCommand`]s to [`bevy_window::Window`]
+    /// (see [`output::process_viewport_commands_system`]), enabled by default.
+    pub enable_viewport_commands: bool,
+    /// If set to `true`, all contexts are driven by [`bevy_picking`] pointers (including custom ones) with
+    /// the [`picking::write_picking_pointer_messages_system`] system, instead of the window pointer, touch and mouse wheel systems.
+    /// Disabled by default.
+    ///
+    /// Touch features of the replaced systems aren't available in this mode: long press secondary clicks
+    /// (see [`EguiContextSettings::long_press`]), [`EguiPenState`] updates and two-finger scrolling.
+    #[cfg(feature = "picking")]
+    pub enable_picking_pointer_input: bool,
 }
 
 impl Default for EguiGlobalSettings {
@@ -290,14 +311,18 @@ impl Default for EguiGlobalSettings {
             enable_focused_non_window_context_updates: true,
             input_system_settings: EguiInputSystemSettings::default(),
             enable_absorb_bevy_input_system: false,
+            absorb_input_settings: EguiAbsorbInputSettings::default(),
             enable_cursor_icon_updates: true,
             enable_ime: true,
+            enable_viewport_commands: true,
+            #[cfg(feature = "picking")]
+            enable_picking_pointer_input: false,
         }
     }
 }
 
 /// A component for storing Egui context settings.
-#[derive(Clone, Debug, Component, PartialEq, Eq, Reflect)]
+#[derive(Clone, Debug, Component, PartialEq, Reflect)]
 pub struct EguiContextSettings {
     /// If set to `true`, a user is expected to call [`egui::Context::run_ui`] or [`egui::Context::begin_pass`] and [`egui::Context::end_pass`] manually.
     pub run_manually: bool,
@@ -317,6 +342,27 @@ pub struct EguiContextSettings {
     pub enable_cursor_icon_updates: bool,
     /// Controls whether the [`process_ime_system`] and [`write_ime_messages_system`] systems are run, enabled by default.
     pub enable_ime: bool,
+    /// Controls whether `bevy_egui` applies [`egui::ViewportCommand`]s to the context's [`bevy_window::Window`]
+    /// (see [`output::process_viewport_commands_system`]), enabled by default.
+    pub enable_viewport_commands: bool,
+    /// If set to `true`, [`egui::Context::show_viewport_immediate`] and [`egui::Context::show_viewport_deferred`]
+    /// spawn native [`bevy_window::Window`]s instead of embedding viewports into the context's render target,
+    /// disabled by default.
+    ///
+    /// Each viewport window gets its own camera and [`EguiContext`] entity sharing the same [`egui::Context`]
+    /// (see [`viewport::manage_egui_viewports_system`]).
+    ///
+    /// The [`EguiSchedule`] of such a context runs single-threaded, as Egui renders immediate viewports
+    /// only on the thread that has the renderer installed (see [`run_egui_context_pass_loop_system`]).
+    pub enable_native_viewports: bool,
+    /// Overrides [`egui::RawInput::system_theme`] of the context.
+    ///
+    /// If set to [`None`] (default), window contexts follow the theme of their window
+    /// (see [`bevy_window::WindowThemeChanged`]), which makes [`egui::ThemePreference::System`] follow the OS theme.
+    pub system_theme_override: Option<bevy_window::WindowTheme>,
+    /// Turns a touch held in place into a secondary button click, which makes context menus reachable on touch screens
+    /// (see [`write_long_press_messages_system`]). Set to [`None`] to disable.
+    pub long_press: Option<EguiLongPressSettings>,
 }
 
 impl Default for EguiContextSettings {
@@ -330,12 +376,74 @@ impl Default for EguiContextSettings {
             input_system_settings: EguiInputSystemSettings::default(),
             enable_cursor_icon_updates: true,
             enable_ime: true,
+            enable_viewport_commands: true,
+            enable_native_viewports: false,
+            system_theme_override: None,
+            long_press: Some(EguiLongPressSettings::default()),
+        }
+    }
+}
+
+/// Long press recognition settings, see [`EguiContextSettings::long_press`].
+#[derive(Clone, Debug, Reflect, PartialEq)]
+pub struct EguiLongPressSettings {
+    /// How long a touch has to be held to become a long press, 0.5 seconds by default.
+    ///
+    /// Keep it below [`egui::InputOptions::max_click_duration`], otherwise Egui detects the long touch on its own.
+    pub duration: std::time::Duration,
+    /// How far (in logical pixels) a touch can move from its start position and still become a long press,
+    /// 10.0 by default.
+    pub max_distance: f32,
+}
+
+impl Default for EguiLongPressSettings {
+    fn default() -> Self {
+        Self {
+            duration: std::time::Duration::from_millis(500),
+            max_distance: 10.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Reflect, PartialEq, Eq)]
+/// Input sources cleared by the [`absorb_bevy_input_system`] system, all are enabled by default.
+///
+/// Messages that belong to a window ([`bevy_input::keyboard::KeyboardInput`], [`bevy_input::mouse::MouseButtonInput`],
+/// [`bevy_input::mouse::MouseWheel`] and [`bevy_input::touch::TouchInput`]) are never cleared, see [`absorb_bevy_input_system`].
+pub struct EguiAbsorbInputSettings {
+    /// Absorb the `ButtonInput<KeyCode>` and `ButtonInput<Key>` resources (pressed modifier keys are preserved).
+    pub absorb_keyboard: bool,
+    /// Absorb the `ButtonInput<MouseButton>` resource.
+    pub absorb_mouse_buttons: bool,
+    /// Absorb the [`bevy_input::mouse::AccumulatedMouseScroll`] resource.
+    pub absorb_mouse_wheel: bool,
+    /// Absorb [`bevy_input::mouse::MouseMotion`] messages and the [`bevy_input::mouse::AccumulatedMouseMotion`] resource.
+    pub absorb_mouse_motion: bool,
+    /// Absorb the [`bevy_input::touch::Touches`] resource.
+    pub absorb_touch: bool,
+    /// Absorb pinch, rotation, double tap and pan gesture messages (see [`bevy_input::gestures`]).
+    pub absorb_gestures: bool,
+    /// Absorb gamepad button and axis messages, and button states of [`bevy_input::gamepad::Gamepad`] components.
+    pub absorb_gamepad: bool,
+}
+
+impl Default for EguiAbsorbInputSettings {
+    fn default() -> Self {
+        Self {
+            absorb_keyboard: true,
+            absorb_mouse_buttons: true,
+            absorb_mouse_wheel: true,
+            absorb_mouse_motion: true,
+            absorb_touch: true,
+            absorb_gestures: true,
+            absorb_gamepad: true,
         }
     }
 }
 
 #[derive(Clone, Debug, Reflect, PartialEq, Eq)]
-/// All the systems are enabled by default. These settings exist within both [`EguiGlobalSettings`] and [`EguiContextSettings`].
+/// All the systems except [`write_gamepad_messages_system`] are enabled by default.
+/// These settings exist within both [`EguiGlobalSettings`] and [`EguiContextSettings`].
 pub struct EguiInputSystemSettings {
     /// Controls running of the [`write_modifiers_keys_state_system`] system.
     pub run_write_modifiers_keys_state_system: bool,
@@ -349,16 +457,39 @@ pub struct EguiInputSystemSettings {
     pub run_write_non_window_pointer_moved_messages_system: bool,
     /// Controls running of the [`write_mouse_wheel_messages_system`] system.
     pub run_write_mouse_wheel_messages_system: bool,
+    /// Controls running of the [`write_mouse_motion_messages_system`] system.
+    pub run_write_mouse_motion_messages_system: bool,
     /// Controls running of the [`write_pinch_gesture_messages_system`] system.
     pub run_write_pinch_gesture_messages_system: bool,
+    /// Controls running of the [`write_rotation_gesture_messages_system`] system.
+    pub run_write_rotation_gesture_messages_system: bool,
+    /// Controls running of the [`write_pan_gesture_messages_system`] system.
+    pub run_write_pan_gesture_messages_system: bool,
+    /// Controls running of the [`write_double_tap_gesture_messages_system`] system.
+    pub run_write_double_tap_gesture_messages_system: bool,
     /// Controls running of the [`write_non_window_touch_messages_system`] system.
     pub run_write_non_window_touch_messages_system: bool,
+    /// Controls running of the [`write_long_press_messages_system`] system.
+    pub run_write_long_press_messages_system: bool,
     /// Controls running of the [`write_keyboard_input_messages_system`] system.
     pub run_write_keyboard_input_messages_system: bool,
     /// Controls running of the [`write_ime_messages_system`] system.
     pub run_write_ime_messages_system: bool,
     /// Controls running of the [`write_file_dnd_messages_system`] system.
     pub run_write_file_dnd_messages_system: bool,
+    /// Controls running of the [`write_gamepad_messages_system`] system, disabled by default.
+    ///
+    /// To navigate Egui with a gamepad, enable it in [`EguiGlobalSettings::input_system_settings`]
+    /// and in [`EguiContextSettings::input_system_settings`] of the contexts that should receive gamepad input.
+    pub run_write_gamepad_messages_system: bool,
+    /// Controls running of the [`virtual_cursor::write_gamepad_virtual_cursor_messages_system`] system.
+    pub run_write_gamepad_virtual_cursor_messages_system: bool,
+    /// Controls running of the [`write_viewport_info_system`] system.
+    pub run_write_viewport_info_system: bool,
+    /// Controls running of the [`picking::write_picking_pointer_messages_system`] system
+    /// (it also has to be enabled with [`EguiGlobalSettings::enable_picking_pointer_input`]).
+    #[cfg(feature = "picking")]
+    pub run_write_picking_pointer_messages_system: bool,
     /// Controls running of the [`write_text_agent_channel_messages_system`] system.
     #[cfg(target_arch = "wasm32")]
     pub run_write_text_agent_channel_messages_system: bool,
@@ -376,11 +507,21 @@ impl Default for EguiInputSystemSettings {
             run_write_window_touch_messages_system: true,
             run_write_non_window_pointer_moved_messages_system: true,
             run_write_mouse_wheel_messages_system: true,
+            run_write_mouse_motion_messages_system: true,
             run_write_pinch_gesture_messages_system: true,
+            run_write_rotation_gesture_messages_system: true,
+            run_write_pan_gesture_messages_system: true,
+            run_write_double_tap_gesture_messages_system: true,
             run_write_non_window_touch_messages_system: true,
+            run_write_long_press_messages_system: true,
             run_write_keyboard_input_messages_system: true,
             run_write_ime_messages_system: true,
             run_write_file_dnd_messages_system: true,
+            run_write_gamepad_messages_system: false,
+            run_write_gamepad_virtual_cursor_messages_system: true,
+            run_write_viewport_info_system: true,
+            #[cfg(feature = "picking")]
+            run_write_picking_pointer_messages_system: true,
             #[cfg(target_arch = "wasm32")]
             run_write_text_agent_channel_messages_system: true,
             #[cfg(all(feature = "manage_clipboard", target_arch = "wasm32"))]
@@ -389,6 +530,39 @@ impl Default for EguiInputSystemSettings {
     }
 }
 
+impl EguiInputSystemSettings {
+    /// Returns the settings with all the systems disabled.
+    pub fn all_disabled() -> Self {
+        Self {
+            run_write_modifiers_keys_state_system: false,
+            run_write_window_pointer_moved_messages: false,
+            run_write_pointer_button_messages: false,
+            run_write_window_touch_messages_system: false,
+            run_write_non_window_pointer_moved_messages_system: false,
+            run_write_mouse_wheel_messages_system: false,
+            run_write_mouse_motion_messages_system: false,
+            run_write_pinch_gesture_messages_system: false,
+            run_write_rotation_gesture_messages_system: false,
+            run_write_pan_gesture_messages_system: false,
+            run_write_double_tap_gesture_messages_system: false,
+            run_write_non_window_touch_messages_system: false,
+            run_write_long_press_messages_system: false,
+            run_write_keyboard_input_messages_system: false,
+            run_write_ime_messages_system: false,
+            run_write_file_dnd_messages_system: false,
+            run_write_gamepad_messages_system: false,
+            run_write_gamepad_virtual_cursor_messages_system: false,
+            run_write_viewport_info_system: false,
+            #[cfg(feature = "picking")]
+            run_write_picking_pointer_messages_system: false,
+            #[cfg(target_arch = "wasm32")]
+            run_write_text_agent_channel_messages_system: false,
+            #[cfg(all(feature = "manage_clipboard", target_arch = "wasm32"))]
+            run_write_web_clipboard_messages_system: false,
+        }
+    }
+}
+
 /// Use this schedule to run your UI systems with the primary Egui context.
 #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct EguiPrimaryContextPass;
@@ -490,25 +664,25 @@ pub struct EguiFullOutput(pub Option<egui::FullOutput>);
 
 /// A resource for accessing clipboard.
 ///
-/// The resource is available only if `manage_clipboard` feature is enabled.
-#[cfg(all(feature = "manage_clipboard", not(target_os = "android")))]
-#[derive(Default, Resource)]
+/// All the operations are delegated to an [`EguiClipboardBackend`]. By default, it's the OS clipboard if the
+/// `manage_clipboard` feature is enabled (and the platform is supported), or [`clipboard::EguiInMemoryClipboard`] otherwise.
+/// A custom backend can be registered by inserting the resource created with [`EguiClipboard::new`]
+/// or by calling [`EguiClipboard::set_backend`].
+#[derive(Resource)]
 pub struct EguiClipboard {
-    #[cfg(not(target_arch = "wasm32"))]
-    clipboard: thread_local::ThreadLocal<Option<RefCell<arboard::Clipboard>>>,
-    #[cfg(all(
-        any(
-            target_os = "linux",
-            target_os = "dragonfly",
-            target_os = "freebsd",
-            target_os = "netbsd",
-            target_os = "openbsd"
-        ),
-        feature = "manage_clipboard"
-    ))]
-    wayland_clipboard: Option<Arc<Mutex<smithay_clipboard::Clipboard>>>,
-    #[cfg(target_arch = "wasm32")]
-    clipboard: web_clipboard::WebClipboard,
+    backend: Box<dyn EguiClipboardBackend>,
+    #[cfg(all(feature = "manage_clipboard", target_arch = "wasm32"))]
+    web_event_receiver: Option<crossbeam_channel::Receiver<web_clipboard::WebClipboardEvent>>,
+}
+
+impl Default for EguiClipboard {
+    fn default() -> Self {
+        Self {
+            backend: clipboard::default_backend(),
+            #[cfg(all(feature = "manage_clipboard", target_arch = "wasm32"))]
+            web_event_receiver: None,
+        }
+    }
 }
 
 /// Is used for storing Egui shapes and textures delta.
@@ -540,6 +714,10 @@ pub struct EguiOutput {
     pub platform_output: egui::PlatformOutput,
     /// Corresponds to [`egui::FullOutput::pixels_per_point`].
     pub pixels_per_point: f32,
+    /// Corresponds to [`egui::FullOutput::viewport_output`].
+    ///
+    /// Viewport commands get drained by [`output::process_viewport_commands_system`].
+    pub viewport_output: egui::OrderedViewportIdMap<egui::ViewportOutput>,
 }
 
 impl Default for EguiOutput {
@@ -547,6 +725,7 @@ impl Default for EguiOutput {
         Self {
             platform_output: egui::PlatformOutput::default(),
             pixels_per_point: 1.0,
+            viewport_output: Default::default(),
         }
     }
 }
@@ -560,10 +739,13 @@ impl Default for EguiOutput {
     EguiContextPointerPosition,
     EguiContextPointerTouchId,
     EguiContextImeState,
+    EguiContextWantsInput,
+    EguiPenState,
     EguiFullOutput,
     EguiRenderOutput,
     EguiOutput
 )]
+#[cfg_attr(feature = "input_focus", require(input_focus::EguiInputFocusState))]
 pub struct EguiContext {
     ctx: egui::Context,
 }
@@ -920,10 +1102,16 @@ impl Plugin for EguiPlugin {
         app.register_type::<EguiContextSettings>();
         app.init_resource::<EguiGlobalSettings>();
         app.init_resource::<ModifierKeysState>();
+        app.init_resource::<WindowModifierKeysState>();
+        app.init_resource::<shortcuts::EguiShortcuts>();
         app.init_resource::<EguiWantsInput>();
         app.init_resource::<WindowToEguiContextMap>();
+        app.init_resource::<EguiWindowImeState>();
+        app.init_resource::<EguiRepaintScheduler>();
+        app.init_resource::<EguiGamepadMapping>();
         app.add_message::<EguiInputEvent>();
         app.add_message::<EguiFileDragAndDropMessage>();
+        app.add_message::<EguiClipboardImagePasted>();
 
         #[cfg(feature = "render")]
         {
@@ -938,7 +1126,6 @@ impl Plugin for EguiPlugin {
         #[cfg(target_arch = "wasm32")]
         app.init_non_send::<SubscribedEvents>();
 
-        #[cfg(all(feature = "manage_clipboard", not(target_os = "android")))]
         app.init_resource::<EguiClipboard>();
 
         app.configure_sets(
@@ -1006,6 +1193,7 @@ impl Plugin for EguiPlugin {
                     .run_if(|s: Res<EguiGlobalSettings>| s.auto_create_primary_context),
                 WindowToEguiContextMap::on_egui_context_added_system,
                 WindowToEguiContextMap::on_egui_context_removed_system,
+                viewport::setup_native_viewports_system,
                 ApplyDeferred,
                 #[cfg(feature = "accesskit")]
                 setup_accesskit_system,
@@ -1022,33 +1210,57 @@ impl Plugin for EguiPlugin {
                         s.run_write_modifiers_keys_state_system
                     })),
                     read_egui_zoom_factor_system,
+                    write_viewport_info_system
+                        .run_if(input_system_is_enabled(|s| {
+                            s.run_write_viewport_info_system
+                        }))
+                        .after(read_egui_zoom_factor_system),
                 )
                     .in_set(EguiInputSet::InitReading),
                 (
-                    write_pointer_moved_and_button_messages_system.run_if(input_system_is_enabled(
-                        |s| {
+                    write_pointer_moved_and_button_messages_system.run_if(
+                        pointer_input_system_is_enabled(|s| {
                             s.run_write_window_pointer_moved_messages
                                 || s.run_write_pointer_button_messages
-                        },
+                        }),
+                    ),
+                    write_window_touch_messages_system.run_if(pointer_input_system_is_enabled(
+                        |s| s.run_write_window_touch_messages_system,
                     )),
-                    write_window_touch_messages_system.run_if(input_system_is_enabled(|s| {
-                        s.run_write_window_touch_messages_system
-                    })),
                 )
                     .in_set(EguiInputSet::FocusContext),
                 (
-                    write_non_window_pointer_moved_messages_system.run_if(input_system_is_enabled(
-                        |s| s.run_write_non_window_pointer_moved_messages_system,
+                    write_non_window_pointer_moved_messages_system.run_if(
+                        pointer_input_system_is_enabled(|s| {
+                            s.run_write_non_window_pointer_moved_messages_system
+                        }),
+                    ),
+                    write_non_window_touch_messages_system.run_if(pointer_input_system_is_enabled(
+                        |s| s.run_write_non_window_touch_messages_system,
                     )),
-                    write_non_window_touch_messages_system.run_if(input_system_is_enabled(|s| {
-                        s.run_write_non_window_touch_messages_system
-                    })),
-                    write_mouse_wheel_messages_system.run_if(input_system_is_enabled(|s| {
-                        s.run_write_mouse_wheel_messages_system
+                    write_long_press_messages_system
+                        .run_if(input_system_is_enabled(|s| {
+                            s.run_write_long_press_messages_system
+                        }))
+                        .after(write_non_window_touch_messages_system),
+                    write_mouse_wheel_messages_system.run_if(pointer_input_system_is_enabled(
+                        |s| s.run_write_mouse_wheel_messages_system,
+                    )),
+                    write_mouse_motion_messages_system.run_if(input_system_is_enabled(|s| {
+                        s.run_write_mouse_motion_messages_system
                     })),
                     write_pinch_gesture_messages_system.run_if(input_system_is_enabled(|s| {
                         s.run_write_pinch_gesture_messages_system
                     })),
+                    write_rotation_gesture_messages_system.run_if(input_system_is_enabled(|s| {
+                        s.run_write_rotation_gesture_messages_system
+                    })),
+                    write_pan_gesture_messages_system.run_if(input_system_is_enabled(|s| {
+                        s.run_write_pan_gesture_messages_system
+                    })),
+                    write_double_tap_gesture_messages_system.run_if(input_system_is_enabled(|s| {
+                        s.run_write_double_tap_gesture_messages_system
+                    })),
                     write_keyboard_input_messages_system.run_if(input_system_is_enabled(|s| {
                         s.run_write_keyboard_input_messages_system
                     })),
@@ -1058,10 +1270,20 @@ impl Plugin for EguiPlugin {
                     write_file_dnd_messages_system.run_if(input_system_is_enabled(|s| {
                         s.run_write_file_dnd_messages_system
                     })),
+                    write_gamepad_messages_system.run_if(input_system_is_enabled(|s| {
+                        s.run_write_gamepad_messages_system
+                    })),
+                    virtual_cursor::write_gamepad_virtual_cursor_messages_system.run_if(
+                        input_system_is_enabled(|s| {
+                            s.run_write_gamepad_virtual_cursor_messages_system
+                        }),
+                    ),
                 )
                     .in_set(EguiInputSet::ReadBevyMessages),
                 (
                     write_egui_input_system,
+                    write_pen_state_system,
+                    shortcuts::write_shortcut_messages_system.after(write_egui_input_system),
                     absorb_bevy_input_system.run_if(|settings: Res<EguiGlobalSettings>| {
                         settings.enable_absorb_bevy_input_system
                     }),
@@ -1071,9 +1293,33 @@ impl Plugin for EguiPlugin {
                 .chain()
                 .in_set(EguiPreUpdateSet::ProcessInput),
         );
+        #[cfg(feature = "input_recording")]
+        {
+            app.init_resource::<recording::EguiInputRecorder>();
+            app.add_systems(
+                PreUpdate,
+                (
+                    recording::replay_egui_input_system
+                        .run_if(resource_exists::<recording::EguiInputReplayer>)
+                        .after(write_modifiers_keys_state_system)
+                        .in_set(EguiInputSet::InitReading),
+                    (
+                        recording::apply_replayed_frame_system
+                            .run_if(resource_exists::<recording::EguiInputReplayer>),
+                        recording::record_egui_input_system,
+                    )
+                        .after(write_egui_input_system)
+                        .in_set(EguiInputSet::WriteEguiEvents),
+                )
+                    .in_set(EguiPreUpdateSet::ProcessInput),
+            );
+            app.add_systems(Last, recording::save_input_recording_on_exit_system);
+        }
         app.add_systems(
             PreUpdate,
-            begin_pass_system.in_set(EguiPreUpdateSet::BeginPass),
+            (viewport::begin_viewport_passes_system, begin_pass_system)
+                .chain()
+                .in_set(EguiPreUpdateSet::BeginPass),
         );
 
         // Web-specific resources and systems.
@@ -1142,7 +1388,11 @@ impl Plugin for EguiPlugin {
         // PostUpdate systems.
         app.add_systems(
             PostUpdate,
-            (run_egui_context_pass_loop_system, end_pass_system)
+            (
+                run_egui_context_pass_loop_system,
+                end_pass_system,
+                viewport::manage_egui_viewports_system,
+            )
                 .chain()
                 .in_set(EguiPostUpdateSet::EndPass),
         );
@@ -1154,18 +1404,60 @@ impl Plugin for EguiPlugin {
                 process_ime_system
                     .run_if(|s: Res<EguiGlobalSettings>| s.enable_ime)
                     .after(process_output_system),
+                process_viewport_commands_system
+                    .run_if(|s: Res<EguiGlobalSettings>| s.enable_viewport_commands)
+                    .after(process_output_system),
+                schedule_repaints_system.after(process_output_system),
             )
                 .in_set(EguiPostUpdateSet::ProcessOutput),
         );
         #[cfg(feature = "picking")]
         if app.is_plugin_added::<bevy_picking::PickingPlugin>() {
+            app.add_systems(
+                PreUpdate,
+                picking::write_picking_pointer_messages_system
+                    .run_if(|s: Res<EguiGlobalSettings>| s.enable_picking_pointer_input)
+                    .after(bevy_picking::PickingSystems::Hover)
+                    .in_set(EguiInputSet::ReadBevyMessages)
+                    .in_set(EguiPreUpdateSet::ProcessInput),
+            );
             app.add_systems(PostUpdate, capture_pointer_input_system);
+            app.add_systems(
+                PostUpdate,
+                picking::process_world_space_ime_system
+                    .run_if(|s: Res<EguiGlobalSettings>| s.enable_ime)
+                    .after(process_output_system)
+                    .in_set(EguiPostUpdateSet::ProcessOutput),
+            );
         } else {
             log::warn!(
                 "The `bevy_egui/picking` feature is enabled, but `PickingPlugin` is not added (if you use Bevy's `DefaultPlugins`, make sure the `bevy/bevy_picking` feature is enabled too)"
             );
         }
 
+        #[cfg(feature = "input_focus")]
+        if app.is_plugin_added::<bevy_input_focus::InputFocusPlugin>() {
+            app.add_observer(input_focus::stop_egui_tab_navigation_observer);
+            app.add_systems(
+                PreUpdate,
+                input_focus::write_tab_navigation_input_system
+                    .after(write_egui_input_system)
+                    .after(bevy_input_focus::InputFocusSystems::Dispatch)
+                    .in_set(EguiInputSet::WriteEguiEvents)
+                    .in_set(EguiPreUpdateSet::ProcessInput),
+            );
+            app.add_systems(
+                PostUpdate,
+                input_focus::write_input_focus_system
+                    .before(bevy_input_focus::InputFocusSystems::FocusChangeEvents)
+                    .in_set(EguiPostUpdateSet::ProcessOutput),
+            );
+        } else {
+            log::warn!(
+                "The `bevy_egui/input_focus` feature is enabled, but `InputFocusPlugin` is not added (make sure it's added before `EguiPlugin`)"
+            );
+        }
+
         // The constants are set to be larger or lower than bevy_ui's ones:
         // https://github.com/bevyengine/bevy/blob/16a6a96a80aab50dcc14c8bb73ef09520f77c09d/crates/bevy_ui/src/picking_backend.rs#L260-L264.
         #[cfg(all(feature = "bevy_ui", feature = "bevy_picking"))]
@@ -1309,12 +1601,18 @@ impl Plugin for EguiPlugin {
             if let Some(winit::raw_window_handle::RawDisplayHandle::Wayland(display)) =
                 raw_display_handle
             {
-                log::debug!("Initializing smithay clipboard");
                 let mut egui_clipboard = app.world_mut().resource_mut::<EguiClipboard>();
-                // Safety: display is also stored as a resource and thus has the same lifetime
-                egui_clipboard.wayland_clipboard = Some(Arc::new(Mutex::new(unsafe {
-                    smithay_clipboard::Clipboard::new(display.display.as_ptr())
-                })));
+                // Don't override a custom backend registered by a user.
+                if egui_clipboard
+                    .backend::<clipboard::ArboardClipboard>()
+                    .is_some()
+                {
+                    log::debug!("Initializing smithay clipboard");
+                    // Safety: display is also stored as a resource and thus has the same lifetime
+                    egui_clipboard.set_backend(unsafe {
+                        clipboard::SmithayClipboard::new(display.display.as_ptr())
+                    });
+                }
             }
         }
     }
@@ -1326,7 +1624,24 @@ fn input_system_is_enabled(
     move |settings| test(&settings.input_system_settings)
 }
 
+/// Window pointer, touch and mouse wheel systems are replaced by
+/// [`picking::write_picking_pointer_messages_system`] if [`EguiGlobalSettings::enable_picking_pointer_input`] is enabled.
+fn pointer_input_system_is_enabled(
+    test: impl Fn(&EguiInputSystemSettings) -> bool,
+) -> impl Fn(Res<EguiGlobalSettings>) -> bool {
+    move |settings| {
+        #[cfg(feature = "picking")]
+        if settings.enable_picking_pointer_input {
+            return false;
+        }
+        test(&settings.input_system_settings)
+    }
+}
+
 /// Contains textures allocated and painted by Egui.
+///
+/// Textures are keyed by context entities, native viewports use the textures of their root context
+/// (see [`viewport::EguiViewport::root_context`]).
 #[cfg(feature = "render")]
 #[derive(Resource, Deref, DerefMut, Default)]
 pub struct EguiManagedTextures(pub HashMap<(Entity, u64), EguiManagedTexture>);
@@ -1389,157 +1704,80 @@ pub fn setup_accesskit_system(
     });
 }
 
-#[cfg(all(feature = "manage_clipboard", not(target_os = "android")))]
 impl EguiClipboard {
+    /// Creates the resource with a custom backend.
+    pub fn new(backend: impl EguiClipboardBackend) -> Self {
+        Self {
+            backend: Box::new(backend),
+            #[cfg(all(feature = "manage_clipboard", target_arch = "wasm32"))]
+            web_event_receiver: None,
+        }
+    }
+
+    /// Replaces the clipboard backend.
+    pub fn set_backend(&mut self, backend: impl EguiClipboardBackend) {
+        self.backend = Box::new(backend);
+    }
+
+    /// Returns the backend if it's of type `T`.
+    pub fn backend<T: EguiClipboardBackend>(&self) -> Option<&T> {
+        (self.backend.as_ref() as &dyn std::any::Any).downcast_ref()
+    }
+
+    /// Returns the backend if it's of type `T`.
+    pub fn backend_mut<T: EguiClipboardBackend>(&mut self) -> Option<&mut T> {
+        (self.backend.as_mut() as &mut dyn std::any::Any).downcast_mut()
+    }
+
     /// Places the text onto the clipboard.
     pub fn set_text(&mut self, contents: &str) {
-        self.set_text_impl(contents);
+        self.backend.set_text(contents);
     }
 
     /// Sets the internal buffer of clipboard contents.
     /// This buffer is used to remember the contents of the last "Paste" event.
-    #[cfg(target_arch = "wasm32")]
+    #[cfg(all(feature = "manage_clipboard", target_arch = "wasm32"))]
     pub fn set_text_internal(&mut self, text: &str) {
-        self.clipboard.set_text_internal(text);
+        if let Some(clipboard) = self.backend_mut::<web_clipboard::WebClipboard>() {
+            clipboard.set_text_internal(text);
+        }
     }
 
     /// Gets clipboard text content. Returns [`None`] if clipboard provider is unavailable or returns an error.
     #[must_use]
     pub fn get_text(&mut self) -> Option<String> {
-        self.get_text_impl()
+        self.backend.get_text()
     }
 
     /// Places an image to the clipboard.
     pub fn set_image(&mut self, image: &egui::ColorImage) {
-        self.set_image_impl(image);
-    }
-
-    /// Receives a clipboard event sent by the `copy`/`cut`/`paste` listeners.
-    #[cfg(target_arch = "wasm32")]
-    pub fn try_receive_clipboard_event(&self) -> Option<web_clipboard::WebClipboardEvent> {
-        self.clipboard.try_receive_clipboard_event()
-    }
-
-    #[cfg(not(target_arch = "wasm32"))]
-    fn set_text_impl(&mut self, contents: &str) {
-        // Try wayland first
-        #[cfg(all(
-            any(
-                target_os = "linux",
-                target_os = "dragonfly",
-                target_os = "freebsd",
-                target_os = "netbsd",
-                target_os = "openbsd"
-            ),
-            feature = "manage_clipboard"
-        ))]
-        if let Some(wayland_clipboard) = &self.wayland_clipboard {
-            let wayland_clipboard = wayland_clipboard.lock().unwrap();
-            wayland_clipboard.store(contents);
-            return;
-        }
-
-        if let Some(mut clipboard) = self.get()
-            && let Err(err) = clipboard.set_text(contents.to_owned())
-        {
-            log::error!("Failed to set clipboard contents: {:?}", err);
-        }
+        self.backend.set_image(image);
     }
 
-    #[cfg(target_arch = "wasm32")]
-    fn set_text_impl(&mut self, contents: &str) {
-        self.clipboard.set_text(contents);
+    /// Gets clipboard image content. Returns [`None`] if the clipboard doesn't hold an image
+    /// or the backend doesn't support reading images.
+    #[must_use]
+    pub fn get_image(&mut self) -> Option<egui::ColorImage> {
+        self.backend.get_image()
     }
 
-    #[cfg(not(target_arch = "wasm32"))]
-    fn get_text_impl(&mut self) -> Option<String> {
-        // Try wayland first
-        #[cfg(all(
-            any(
-                target_os = "linux",
-                target_os = "dragonfly",
-                target_os = "freebsd",
-                target_os = "netbsd",
-                target_os = "openbsd"
-            ),
-            feature = "manage_clipboard"
-        ))]
-        if let Some(wayland_clipboard) = &self.wayland_clipboard {
-            let wayland_clipboard = wayland_clipboard.lock().unwrap();
-            return match wayland_clipboard.load() {
-                Ok(text) => Some(text),
-                Err(err) => {
-                    log::error!("Failed to get clipboard content via smithay: {err}");
-                    None
-                }
-            };
-        }
-
-        if let Some(mut clipboard) = self.get() {
-            match clipboard.get_text() {
-                Ok(contents) => return Some(contents),
-                // We don't want to spam with this error as it usually means that the clipboard is either empty or has an incompatible format (e.g. image).
-                Err(arboard::Error::ContentNotAvailable) => return Some("".to_string()),
-                Err(err) => log::error!("Failed to get clipboard contents: {:?}", err),
-            }
+    /// Receives a clipboard event sent by the `copy`/`cut`/`paste` listeners.
+    #[cfg(all(feature = "manage_clipboard", target_arch = "wasm32"))]
+    pub fn try_receive_clipboard_event(&self) -> Option<web_clipboard::WebClipboardEvent> {
+        let Some(rx) = &self.web_event_receiver else {
+            log::error!("Web clipboard event receiver isn't initialized");
+            return None;
         };
-        None
-    }
 
-    #[cfg(target_arch = "wasm32")]
-    #[allow(clippy::unnecessary_wraps)]
-    fn get_text_impl(&mut self) -> Option<String> {
-        self.clipboard.get_text()
-    }
-
-    #[cfg(not(target_arch = "wasm32"))]
-    fn set_image_impl(&mut self, image: &egui::ColorImage) {
-        // Try wayland first
-        #[cfg(all(
-            any(
-                target_os = "linux",
-                target_os = "dragonfly",
-                target_os = "freebsd",
-                target_os = "netbsd",
-                target_os = "openbsd"
-            ),
-            feature = "manage_clipboard"
-        ))]
-        if self.wayland_clipboard.is_some() {
-            log::error!("Setting image to clipboard via smithay is not supported");
-            return;
-        }
-
-        if let Some(mut clipboard) = self.get()
-            && let Err(err) = clipboard.set_image(arboard::ImageData {
-                width: image.width(),
-                height: image.height(),
-                bytes: std::borrow::Cow::Borrowed(bytemuck::cast_slice(&image.pixels)),
-            })
-        {
-            log::error!("Failed to set clipboard contents: {:?}", err);
+        match rx.try_recv() {
+            Ok(event) => Some(event),
+            Err(crossbeam_channel::TryRecvError::Empty) => None,
+            Err(err @ crossbeam_channel::TryRecvError::Disconnected) => {
+                log::error!("Failed to read a web clipboard event: {err:?}");
+                None
+            }
         }
     }
-
-    #[cfg(target_arch = "wasm32")]
-    fn set_image_impl(&mut self, image: &egui::ColorImage) {
-        self.clipboard.set_image(image);
-    }
-
-    #[cfg(not(target_arch = "wasm32"))]
-    fn get(&self) -> Option<RefMut<'_, arboard::Clipboard>> {
-        self.clipboard
-            .get_or(|| {
-                arboard::Clipboard::new()
-                    .map(RefCell::new)
-                    .map_err(|err| {
-                        log::error!("Failed to initialize arboard clipboard: {:?}", err);
-                    })
-                    .ok()
-            })
-            .as_ref()
-            .map(|cell| cell.borrow_mut())
-    }
 }
 
 /// Contains the order value that we add to [`bevy_camera::Camera::order`] for [`PointerHits`]
@@ -1608,10 +1846,13 @@ pub fn update_egui_textures_system(
     mut egui_render_output: Query<(Entity, &mut EguiRenderOutput)>,
     mut egui_managed_textures: ResMut<EguiManagedTextures>,
     mut image_assets: ResMut<Assets<Image>>,
+    viewport_links: viewport::EguiViewportLinks,
 ) {
     use bevy_image::TextureAccessError;
 
     for (entity, mut egui_render_output) in egui_render_output.iter_mut() {
+        // Native viewports of the same Egui context share the textures of its root context.
+        let entity = viewport_links.root_context(entity);
         for (texture_id, image_deltas) in egui_render_output.textures_delta.set.drain() {
             let texture_id = match texture_id {
                 egui::TextureId::Managed(texture_id) => texture_id,
@@ -1687,11 +1928,13 @@ pub fn free_egui_textures_system(
     mut egui_managed_textures: ResMut<EguiManagedTextures>,
     mut image_assets: ResMut<Assets<Image>>,
     mut image_event_reader: MessageReader<AssetEvent<Image>>,
+    viewport_links: viewport::EguiViewportLinks,
 ) {
     for (entity, mut egui_render_output) in egui_render_output.iter_mut() {
         for texture_id in egui_render_output.textures_delta.free.drain() {
             if let egui::TextureId::Managed(texture_id) = texture_id {
-                let managed_texture = egui_managed_textures.remove(&(entity, texture_id));
+                let managed_texture = egui_managed_textures
+                    .remove(&(viewport_links.root_context(entity), texture_id));
                 if let Some(managed_texture) = managed_texture {
                     image_assets.remove(&managed_texture.handle);
                 }
@@ -1866,7 +2109,14 @@ pub struct EguiPassQuery {
 
 /// Runs Egui contexts with the [`EguiSchedule`] component. If there are no contexts with
 /// this component, runs the [`EguiPrimaryContextPass`] schedule once independently.
-pub fn run_egui_context_pass_loop_system(world: &mut World) -> Result {
+///
+/// Schedules of contexts with [`EguiContextSettings::enable_native_viewports`] run single-threaded on the thread
+/// of this system, which has the immediate viewport renderer installed (see [`viewport::install_immediate_viewport_renderer`]),
+/// as Egui stores it in a thread-local variable.
+pub fn run_egui_context_pass_loop_system(
+    world: &mut World,
+    mut single_threaded_schedules: Local<HashSet<InternedScheduleLabel>>,
+) -> Result {
     let mut contexts_query = world.query::<EguiPassQuery>();
     let mut used_schedules = HashSet::<InternedScheduleLabel>::default();
 
@@ -1882,17 +2132,29 @@ pub fn run_egui_context_pass_loop_system(world: &mut World) -> Result {
                 egui_context.context.get_mut().clone(),
                 egui_context.input.take(),
                 egui_context.schedule.clone(),
+                egui_context.settings.enable_native_viewports,
             ))
         })
         .collect();
 
-    for (entity, ctx, input, EguiSchedule(schedule)) in &mut contexts {
+    for (entity, ctx, input, EguiSchedule(schedule), enable_native_viewports) in &mut contexts {
         if !used_schedules.insert(*schedule) {
             panic!(
                 "Each Egui context must have a unique schedule (attempted to reuse schedule {schedule:?})"
             );
         }
 
+        if *enable_native_viewports {
+            viewport::install_immediate_viewport_renderer();
+            if !single_threaded_schedules.contains(schedule)
+                && let Some(mut schedules) = world.get_resource_mut::<Schedules>()
+                && let Some(schedule_to_update) = schedules.get_mut(*schedule)
+            {
+                schedule_to_update.set_executor(SingleThreadedExecutor::new());
+                single_threaded_schedules.insert(*schedule);
+            }
+        }
+
         let mut result = Ok(());
         let output = ctx.run_ui(input.take(), |_| {
             result = world.try_run_schedule(*schedule);
@@ -1908,6 +2170,46 @@ pub fn run_egui_context_pass_loop_system(world: &mut World) -> Result {
     Ok(())
 }
 
+/// Extension for the [`Commands`] trait.
+pub trait BevyEguiCommandsExt {
+    /// Sets keyboard focus on an Egui context.
+    ///
+    /// For a non-window context (e.g. rendered to an image), it inserts the [`FocusedNonWindowEguiContext`] resource.
+    /// For a window context, it removes the resource and focuses the window.
+    /// The change is reflected in [`egui::RawInput::focused`] by [`input::write_egui_input_system`], which also triggers
+    /// [`input::EguiContextFocused`] and [`input::EguiContextUnfocused`].
+    ///
+    /// Focus is tracked per window, so if a window has several contexts, all of them get focused,
+    /// and Egui of each context decides whether it wants keyboard input (see [`input::EguiContextWantsInput`]).
+    /// Entities without the [`EguiContext`] component are ignored.
+    fn focus_egui_context(&mut self, context: Entity);
+}
+
+impl BevyEguiCommandsExt for Commands<'_, '_> {
+    fn focus_egui_context(&mut self, context: Entity) {
+        self.queue(move |world: &mut World| {
+            if world.get::<EguiContext>(context).is_none() {
+                log::warn!("Can't focus an entity without an Egui context ({context:?})");
+                return;
+            }
+            let Some(map) = world.get_resource::<WindowToEguiContextMap>() else {
+                return;
+            };
+            let Some(window) = map.context_to_window.get(&context).copied() else {
+                world.insert_resource(FocusedNonWindowEguiContext(context));
+                return;
+            };
+
+            world.remove_resource::<FocusedNonWindowEguiContext>();
+            if let Some(mut window) = world.get_mut::<bevy_window::Window>(window)
+                && !window.focused
+            {
+                window.focused = true;
+            }
+        });
+    }
+}
+
 /// Extension for the [`EntityCommands`] trait.
 #[cfg(feature = "picking")]
 pub trait BevyEguiEntityCommandsExt {
@@ -1,14 +1,26 @@
 use crate::{
     EguiContext, EguiContextSettings, EguiFullOutput, EguiGlobalSettings, EguiOutput,
-    EguiRenderOutput, helpers, input::WindowToEguiContextMap,
+    EguiRenderOutput, EguiZoomFactor, helpers,
+    input::{EguiClipboardImagePasted, EguiInputEvent, EguiInputSource, WindowToEguiContextMap},
+    viewport::EguiViewportLinks,
 };
 use bevy_ecs::{
     entity::Entity,
     message::MessageWriter,
-    system::{Commands, Local, Query, Res},
+    resource::Resource,
+    system::{Commands, Local, Query, Res, ResMut},
 };
 use bevy_platform::collections::HashMap;
-use bevy_window::{CursorIcon, RequestRedraw};
+use bevy_time::{Real, Time};
+use bevy_window::{
+    CursorIcon, CursorOptions, MonitorSelection, RequestRedraw, Window, WindowCloseRequested,
+    WindowMode, WindowPosition,
+};
+use bevy_winit::{UpdateMode, WinitSettings};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 /// Reads Egui output.
 #[allow(clippy::too_many_arguments)]
@@ -22,7 +34,6 @@ pub fn process_output_system(
         &mut EguiOutput,
         &EguiContextSettings,
     )>,
-    #[cfg(all(feature = "manage_clipboard", not(target_os = "android")))]
     mut egui_clipboard: bevy_ecs::system::ResMut<crate::EguiClipboard>,
     mut request_redraw_writer: MessageWriter<RequestRedraw>,
     mut last_cursor_icon: Local<HashMap<Entity, egui::CursorIcon>>,
@@ -46,7 +57,7 @@ pub fn process_output_system(
             shapes,
             textures_delta,
             pixels_per_point,
-            viewport_output: _,
+            viewport_output,
         } = full_output;
         let paint_jobs = ctx.tessellate(shapes, pixels_per_point);
 
@@ -54,19 +65,17 @@ pub fn process_output_system(
         render_output.textures_delta = textures_delta;
         egui_output.platform_output = platform_output;
         egui_output.pixels_per_point = pixels_per_point;
+        egui_output.viewport_output = viewport_output;
 
         for command in &egui_output.platform_output.commands {
             match command {
-                egui::OutputCommand::CopyText(_text) =>
-                {
-                    #[cfg(all(feature = "manage_clipboard", not(target_os = "android")))]
-                    if !_text.is_empty() {
-                        egui_clipboard.set_text(_text);
+                egui::OutputCommand::CopyText(text) => {
+                    if !text.is_empty() {
+                        egui_clipboard.set_text(text);
                     }
                 }
-                egui::OutputCommand::CopyImage(_image) => {
-                    #[cfg(all(feature = "manage_clipboard", not(target_os = "android")))]
-                    egui_clipboard.set_image(_image);
+                egui::OutputCommand::CopyImage(image) => {
+                    egui_clipboard.set_image(image);
                 }
                 egui::OutputCommand::OpenUrl(_url) => {
                     #[cfg(feature = "open_url")]
@@ -116,3 +125,546 @@ pub fn process_output_system(
         request_redraw_writer.write(RequestRedraw);
     }
 }
+
+/// Keeps track of delayed repaints requested by Egui contexts (see [`egui::Context::request_repaint_after`]).
+///
+/// Repaint requests are recorded by the [`egui::Context::set_request_repaint_callback`] callback
+/// (see [`crate::input::WindowToEguiContextMap::on_egui_context_added_system`]) and turned into deadlines
+/// by [`schedule_repaints_system`].
+#[derive(Resource, Default)]
+pub struct EguiRepaintScheduler {
+    requests: EguiRepaintRequests,
+    deadlines: HashMap<Entity, Duration>,
+    winit_settings_override: Option<WinitSettingsOverride>,
+}
+
+impl EguiRepaintScheduler {
+    /// Returns a handle for recording repaint requests, which can be moved into callbacks.
+    pub fn requests(&self) -> EguiRepaintRequests {
+        self.requests.clone()
+    }
+
+    /// Returns the pending repaint deadline of a context, measured as [`Time<Real>::elapsed`].
+    pub fn deadline(&self, context: Entity) -> Option<Duration> {
+        self.deadlines.get(&context).copied()
+    }
+
+    /// Returns the earliest pending repaint deadline, measured as [`Time<Real>::elapsed`].
+    pub fn next_deadline(&self) -> Option<Duration> {
+        self.deadlines.values().min().copied()
+    }
+}
+
+/// A shared queue of repaint requests, see [`EguiRepaintScheduler::requests`].
+#[derive(Clone, Default)]
+pub struct EguiRepaintRequests(Arc<Mutex<Vec<(Entity, Duration)>>>);
+
+impl EguiRepaintRequests {
+    /// Requests a context to be repainted after the `delay`.
+    pub fn request_repaint_after(&self, context: Entity, delay: Duration) {
+        self.0.lock().unwrap().push((context, delay));
+    }
+}
+
+/// Stores the update modes configured by a user, so that they can be restored once there are no pending repaints.
+struct WinitSettingsOverride {
+    original: [UpdateMode; 2],
+    applied: [UpdateMode; 2],
+}
+
+/// Turns repaint requests into deadlines and shortens the [`UpdateMode::Reactive`] wait time of [`WinitSettings`],
+/// so that the event loop wakes up in time to repaint.
+///
+/// Once there are no pending deadlines, the original update modes are restored.
+pub fn schedule_repaints_system(
+    mut repaint_scheduler: ResMut<EguiRepaintScheduler>,
+    winit_settings: Option<ResMut<WinitSettings>>,
+    time: Res<Time<Real>>,
+) {
+    let now = time.elapsed();
+    let scheduler = &mut *repaint_scheduler;
+
+    for (context, delay) in std::mem::take(&mut *scheduler.requests.0.lock().unwrap()) {
+        let Some(deadline) = now.checked_add(delay) else {
+            continue;
+        };
+        scheduler
+            .deadlines
+            .entry(context)
+            .and_modify(|pending_deadline| *pending_deadline = (*pending_deadline).min(deadline))
+            .or_insert(deadline);
+    }
+    // The current update has already repainted contexts with expired deadlines.
+    scheduler.deadlines.retain(|_, deadline| *deadline > now);
+
+    let Some(mut winit_settings) = winit_settings else {
+        return;
+    };
+    let current = [winit_settings.focused_mode, winit_settings.unfocused_mode];
+    let original = match &scheduler.winit_settings_override {
+        // Users may have changed the settings since we last updated them.
+        Some(settings_override) if settings_override.applied == current => {
+            settings_override.original
+        }
+        _ => current,
+    };
+
+    let remaining = scheduler.next_deadline().map(|deadline| deadline - now);
+    let target = original.map(|mode| match (mode, remaining) {
+        (
+            UpdateMode::Reactive {
+                wait,
+                react_to_device_events,
+                react_to_user_events,
+                react_to_window_events,
+            },
+            Some(remaining),
+        ) => UpdateMode::Reactive {
+            wait: wait.min(remaining),
+            react_to_device_events,
+            react_to_user_events,
+            react_to_window_events,
+        },
+        (mode, _) => mode,
+    });
+
+    if target != current {
+        [winit_settings.focused_mode, winit_settings.unfocused_mode] = target;
+    }
+    scheduler.winit_settings_override = (target != original).then_some(WinitSettingsOverride {
+        original,
+        applied: target,
+    });
+}
+
+/// Applies [`egui::ViewportCommand`]s to the [`Window`]s (and their [`CursorOptions`]) that
+/// render the respective viewports.
+///
+/// The [`egui::ViewportId::ROOT`] viewport commands are applied to the window a context renders to,
+/// commands of other viewports are applied to their native windows (see [`crate::viewport`]).
+///
+/// Commands are drained from [`EguiOutput::viewport_output`], so each of them is applied only once.
+/// Commands that don't have a Bevy counterpart are ignored.
+#[allow(clippy::too_many_arguments)]
+pub fn process_viewport_commands_system(
+    mut context_query: Query<(
+        Entity,
+        &mut EguiOutput,
+        &EguiZoomFactor,
+        &EguiContextSettings,
+    )>,
+    mut windows: Query<(&mut Window, Option<&mut CursorOptions>)>,
+    mut egui_clipboard: bevy_ecs::system::ResMut<crate::EguiClipboard>,
+    mut window_close_requested_writer: MessageWriter<WindowCloseRequested>,
+    mut egui_input_message_writer: MessageWriter<EguiInputEvent>,
+    mut image_pasted_writer: MessageWriter<EguiClipboardImagePasted>,
+    viewport_links: EguiViewportLinks,
+) {
+    for (entity, mut egui_output, &EguiZoomFactor { zoom_factor }, settings) in
+        context_query.iter_mut()
+    {
+        for (&viewport_id, viewport_output) in egui_output.viewport_output.iter_mut() {
+            let commands = std::mem::take(&mut viewport_output.commands);
+            if commands.is_empty() || !settings.enable_viewport_commands {
+                continue;
+            }
+
+            let Some(window_entity) = viewport_links.viewport_window(entity, viewport_id) else {
+                continue;
+            };
+            let Ok((mut window, mut cursor_options)) = windows.get_mut(window_entity) else {
+                continue;
+            };
+            let viewport_context = viewport_links
+                .viewport_context(entity, viewport_id)
+                .unwrap_or(entity);
+
+            for command in commands {
+                let Some(command) = apply_viewport_command(
+                    command,
+                    &mut window,
+                    cursor_options.as_deref_mut(),
+                    zoom_factor,
+                ) else {
+                    continue;
+                };
+
+                match command {
+                    egui::ViewportCommand::Close => {
+                        window_close_requested_writer.write(WindowCloseRequested {
+                            window: window_entity,
+                        });
+                    }
+                    egui::ViewportCommand::RequestCut => {
+                        egui_input_message_writer.write(EguiInputEvent {
+                            context: viewport_context,
+                            source: EguiInputSource::ViewportCommand,
+                            event: egui::Event::Cut,
+                        });
+                    }
+                    egui::ViewportCommand::RequestCopy => {
+                        egui_input_message_writer.write(EguiInputEvent {
+                            context: viewport_context,
+                            source: EguiInputSource::ViewportCommand,
+                            event: egui::Event::Copy,
+                        });
+                    }
+                    egui::ViewportCommand::RequestPaste => match egui_clipboard.get_text() {
+                        Some(contents) if !contents.is_empty() => {
+                            egui_input_message_writer.write(EguiInputEvent {
+                                context: viewport_context,
+                                source: EguiInputSource::ViewportCommand,
+                                event: egui::Event::Paste(contents),
+                            });
+                        }
+                        _ => {
+                            if let Some(image) = egui_clipboard.get_image() {
+                                image_pasted_writer.write(EguiClipboardImagePasted {
+                                    context: viewport_context,
+                                    image,
+                                });
+                            }
+                        }
+                    },
+                    command => {
+                        bevy_log::debug!("Unsupported viewport command: {command:?}");
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Applies an [`egui::ViewportCommand`] to a [`Window`] (and its [`CursorOptions`]).
+///
+/// Returns the command back if it can't be applied to the window directly
+/// (such as [`egui::ViewportCommand::Close`] or clipboard requests).
+pub fn apply_viewport_command(
+    command: egui::ViewportCommand,
+    window: &mut Window,
+    cursor_options: Option<&mut CursorOptions>,
+    zoom_factor: f32,
+) -> Option<egui::ViewportCommand> {
+    match command {
+        egui::ViewportCommand::Title(title) => window.title = title,
+        egui::ViewportCommand::Transparent(transparent) => window.transparent = transparent,
+        egui::ViewportCommand::Visible(visible) => window.visible = visible,
+        egui::ViewportCommand::StartDrag => window.start_drag_move(),
+        egui::ViewportCommand::OuterPosition(position) => {
+            let position =
+                helpers::egui_pos2_into_vec2(position) * zoom_factor * window.scale_factor();
+            window.position = WindowPosition::At(position.as_ivec2());
+        }
+        egui::ViewportCommand::InnerSize(size) => {
+            let size = helpers::egui_vec2_into_vec2(size) * zoom_factor;
+            window.resolution.set(size.x, size.y);
+        }
+        egui::ViewportCommand::MinInnerSize(size) => {
+            let size = helpers::egui_vec2_into_vec2(size) * zoom_factor;
+            window.resize_constraints.min_width = size.x;
+            window.resize_constraints.min_height = size.y;
+        }
+        egui::ViewportCommand::MaxInnerSize(size) => {
+            let size = helpers::egui_vec2_into_vec2(size) * zoom_factor;
+            window.resize_constraints.max_width = size.x;
+            window.resize_constraints.max_height = size.y;
+        }
+        egui::ViewportCommand::BeginResize(direction) => {
+            window.start_drag_resize(helpers::egui_to_bevy_resize_direction(direction));
+        }
+        egui::ViewportCommand::Resizable(resizable) => window.resizable = resizable,
+        egui::ViewportCommand::EnableButtons {
+            close,
+            minimized,
+            maximize,
+        } => {
+            window.enabled_buttons.close = close;
+            window.enabled_buttons.minimize = minimized;
+            window.enabled_buttons.maximize = maximize;
+        }
+        egui::ViewportCommand::Minimized(minimized) => window.set_minimized(minimized),
+        egui::ViewportCommand::Maximized(maximized) => window.set_maximized(maximized),
+        egui::ViewportCommand::Fullscreen(fullscreen) => {
+            window.mode = if fullscreen {
+                WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+            } else {
+                WindowMode::Windowed
+            };
+        }
+        egui::ViewportCommand::SetMonitor(index) => {
+            window.mode = WindowMode::BorderlessFullscreen(MonitorSelection::Index(index));
+        }
+        egui::ViewportCommand::Decorations(decorations) => window.decorations = decorations,
+        egui::ViewportCommand::WindowLevel(level) => {
+            window.window_level = helpers::egui_to_bevy_window_level(level);
+        }
+        egui::ViewportCommand::IMERect(rect) => {
+            window.ime_position = helpers::egui_pos2_into_vec2(rect.min) * zoom_factor;
+        }
+        egui::ViewportCommand::IMEAllowed(allowed) => window.ime_enabled = allowed,
+        egui::ViewportCommand::Focus => window.focused = true,
+        egui::ViewportCommand::SetTheme(theme) => {
+            window.window_theme = helpers::egui_to_bevy_window_theme(theme);
+        }
+        egui::ViewportCommand::CursorPosition(position) => {
+            window.set_cursor_position(Some(helpers::egui_pos2_into_vec2(position) * zoom_factor));
+        }
+        egui::ViewportCommand::CursorGrab(grab) => {
+            if let Some(cursor_options) = cursor_options {
+                cursor_options.grab_mode = helpers::egui_to_bevy_cursor_grab_mode(grab);
+            }
+        }
+        egui::ViewportCommand::CursorVisible(visible) => {
+            if let Some(cursor_options) = cursor_options {
+                cursor_options.visible = visible;
+            }
+        }
+        egui::ViewportCommand::MousePassthrough(passthrough) => {
+            if let Some(cursor_options) = cursor_options {
+                cursor_options.hit_test = !passthrough;
+            }
+        }
+        command => return Some(command),
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clipboard::EguiInMemoryClipboard;
+    use bevy_app::{App, Update};
+    use bevy_ecs::message::Messages;
+
+    fn app_with_viewport_commands_system() -> (App, Entity, Entity) {
+        let mut app = App::new();
+        app.add_message::<WindowCloseRequested>()
+            .add_message::<EguiInputEvent>()
+            .add_message::<EguiClipboardImagePasted>()
+            .init_resource::<WindowToEguiContextMap>()
+            .add_systems(Update, process_viewport_commands_system)
+            .insert_resource(crate::EguiClipboard::new(EguiInMemoryClipboard::default()));
+
+        let window = app
+            .world_mut()
+            .spawn((Window::default(), CursorOptions::default()))
+            .id();
+        let context = app.world_mut().spawn(EguiContext::default()).id();
+        let mut map = app.world_mut().resource_mut::<WindowToEguiContextMap>();
+        map.window_to_contexts
+            .entry(window)
+            .or_default()
+            .insert(context);
+        map.context_to_window.insert(context, window);
+        (app, window, context)
+    }
+
+    fn send_viewport_commands(
+        app: &mut App,
+        context: Entity,
+        commands: impl IntoIterator<Item = egui::ViewportCommand>,
+    ) {
+        let mut egui_output = app.world_mut().get_mut::<EguiOutput>(context).unwrap();
+        egui_output.viewport_output.insert(
+            egui::ViewportId::ROOT,
+            egui::ViewportOutput {
+                parent: egui::ViewportId::ROOT,
+                class: egui::ViewportClass::Root,
+                builder: egui::ViewportBuilder::default(),
+                viewport_ui_cb: None,
+                commands: commands.into_iter().collect(),
+                repaint_delay: std::time::Duration::ZERO,
+            },
+        );
+    }
+
+    #[test]
+    fn viewport_commands_update_window() {
+        let (mut app, window, context) = app_with_viewport_commands_system();
+        send_viewport_commands(
+            &mut app,
+            context,
+            [
+                egui::ViewportCommand::Title("Egui title".to_owned()),
+                egui::ViewportCommand::Decorations(false),
+                egui::ViewportCommand::Fullscreen(true),
+                egui::ViewportCommand::CursorVisible(false),
+                egui::ViewportCommand::CursorGrab(egui::CursorGrab::Locked),
+            ],
+        );
+        app.update();
+
+        let window_component = app.world().get::<Window>(window).unwrap();
+        assert_eq!(window_component.title, "Egui title");
+        assert!(!window_component.decorations);
+        assert_eq!(
+            window_component.mode,
+            WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+        );
+        let cursor_options = app.world().get::<CursorOptions>(window).unwrap();
+        assert!(!cursor_options.visible);
+        assert_eq!(
+            cursor_options.grab_mode,
+            bevy_window::CursorGrabMode::Locked
+        );
+
+        // Commands are drained and aren't re-applied on the next update.
+        app.world_mut().get_mut::<Window>(window).unwrap().title = "User title".to_owned();
+        app.update();
+        assert_eq!(
+            app.world().get::<Window>(window).unwrap().title,
+            "User title"
+        );
+    }
+
+    #[test]
+    fn close_viewport_command_requests_window_close() {
+        let (mut app, window, context) = app_with_viewport_commands_system();
+        send_viewport_commands(&mut app, context, [egui::ViewportCommand::Close]);
+        app.update();
+
+        let messages = app.world().resource::<Messages<WindowCloseRequested>>();
+        let mut messages = messages.iter_current_update_messages();
+        assert!(matches!(
+            messages.next(),
+            Some(WindowCloseRequested { window: close_requested }) if *close_requested == window
+        ));
+        assert!(messages.next().is_none());
+    }
+
+    #[test]
+    fn paste_viewport_command_reads_clipboard_backend() {
+        let (mut app, _window, context) = app_with_viewport_commands_system();
+        app.world_mut()
+            .resource_mut::<crate::EguiClipboard>()
+            .set_text("pasted");
+        send_viewport_commands(&mut app, context, [egui::ViewportCommand::RequestPaste]);
+        app.update();
+
+        let messages = app.world().resource::<Messages<EguiInputEvent>>();
+        let mut messages = messages.iter_current_update_messages();
+        assert!(matches!(
+            messages.next(),
+            Some(EguiInputEvent { context: paste_context, event: egui::Event::Paste(text), .. })
+                if *paste_context == context && text == "pasted"
+        ));
+        assert!(messages.next().is_none());
+    }
+
+    #[test]
+    fn copy_output_commands_write_to_clipboard_backend() {
+        let mut app = App::new();
+        app.add_message::<RequestRedraw>()
+            .init_resource::<WindowToEguiContextMap>()
+            .init_resource::<EguiGlobalSettings>()
+            .insert_resource(crate::EguiClipboard::new(EguiInMemoryClipboard::default()))
+            .add_systems(Update, process_output_system);
+        let image = egui::ColorImage::filled([2, 2], egui::Color32::RED);
+        let context = app
+            .world_mut()
+            .spawn((
+                EguiContext::default(),
+                EguiFullOutput::default(),
+                EguiRenderOutput::default(),
+                EguiOutput::default(),
+                EguiContextSettings::default(),
+            ))
+            .id();
+        let send_commands = |app: &mut App, commands: Vec<egui::OutputCommand>| {
+            let mut context_entity = app.world_mut().entity_mut(context);
+            let ctx = context_entity
+                .get_mut::<EguiContext>()
+                .unwrap()
+                .get_mut()
+                .clone();
+            let mut full_output = ctx.run_ui(egui::RawInput::default(), |_| {});
+            full_output.textures_delta.clear();
+            full_output.platform_output.commands = commands;
+            context_entity.get_mut::<EguiFullOutput>().unwrap().0 = Some(full_output);
+            app.update();
+        };
+        let clipboard = |app: &App| {
+            app.world()
+                .resource::<crate::EguiClipboard>()
+                .backend::<EguiInMemoryClipboard>()
+                .unwrap()
+                .clone()
+        };
+
+        send_commands(
+            &mut app,
+            vec![egui::OutputCommand::CopyText("copied".to_owned())],
+        );
+        assert_eq!(clipboard(&app).text.as_deref(), Some("copied"));
+
+        // Empty text doesn't overwrite the clipboard contents.
+        send_commands(&mut app, vec![egui::OutputCommand::CopyText(String::new())]);
+        assert_eq!(clipboard(&app).text.as_deref(), Some("copied"));
+
+        send_commands(
+            &mut app,
+            vec![egui::OutputCommand::CopyImage(image.clone())],
+        );
+        let contents = clipboard(&app);
+        assert_eq!(contents.text, None);
+        assert_eq!(contents.image, Some(image));
+    }
+
+    #[test]
+    fn delayed_repaints_shorten_reactive_wait_time() {
+        let mut app = App::new();
+        app.init_resource::<EguiRepaintScheduler>()
+            .init_resource::<Time<Real>>()
+            .insert_resource(WinitSettings::desktop_app())
+            .add_systems(Update, schedule_repaints_system);
+        let context = app.world_mut().spawn_empty().id();
+        let advance_time = |app: &mut App, duration: Duration| {
+            app.world_mut()
+                .resource_mut::<Time<Real>>()
+                .update_with_duration(duration);
+        };
+        let focused_mode = |app: &App| app.world().resource::<WinitSettings>().focused_mode;
+        advance_time(&mut app, Duration::ZERO);
+
+        let requests = app.world().resource::<EguiRepaintScheduler>().requests();
+        requests.request_repaint_after(context, Duration::from_millis(500));
+        requests.request_repaint_after(context, Duration::from_secs(1));
+        app.update();
+        assert_eq!(
+            app.world()
+                .resource::<EguiRepaintScheduler>()
+                .deadline(context),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(
+            focused_mode(&app),
+            UpdateMode::reactive(Duration::from_millis(500))
+        );
+
+        advance_time(&mut app, Duration::from_millis(200));
+        app.update();
+        assert_eq!(
+            focused_mode(&app),
+            UpdateMode::reactive(Duration::from_millis(300))
+        );
+
+        // Once the deadline passes, the original settings are restored.
+        advance_time(&mut app, Duration::from_millis(400));
+        app.update();
+        assert_eq!(
+            app.world()
+                .resource::<EguiRepaintScheduler>()
+                .next_deadline(),
+            None
+        );
+        assert_eq!(
+            focused_mode(&app),
+            UpdateMode::reactive(Duration::from_secs(5))
+        );
+        assert_eq!(
+            app.world().resource::<WinitSettings>().unfocused_mode,
+            UpdateMode::reactive_low_power(Duration::from_secs(60))
+        );
+    }
+}
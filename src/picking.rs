@@ -1,23 +1,36 @@
 use crate::{
-    EguiContext, helpers,
-    input::{EguiContextPointerPosition, HoveredNonWindowEguiContext},
+    EguiContext, EguiContextSettings, EguiGlobalSettings, EguiOutput, EguiZoomFactor, helpers,
+    input::{
+        EguiContextImeState, EguiContextPointerPosition, EguiInputEvent, EguiInputSource,
+        EguiWindowImeState, FocusedNonWindowEguiContext, HoveredNonWindowEguiContext,
+        ModifierKeysState, WindowModifierKeysState, WindowToEguiContextMap,
+    },
 };
+use bevy_asset::Assets;
 use bevy_camera::{Camera, NormalizedRenderTarget, RenderTarget};
 use bevy_ecs::{
-    change_detection::Res,
+    change_detection::{Res, ResMut},
     component::Component,
-    entity::Entity,
+    entity::{ContainsEntity, Entity},
     error::Result,
+    message::{MessageReader, MessageWriter},
     observer::On,
     prelude::{Commands, Query, With},
+    system::{Local, NonSendMarker},
 };
-use bevy_math::Ray3d;
+use bevy_input::{mouse::MouseScrollUnit, touch::TouchPhase};
+use bevy_log as log;
+use bevy_math::{Ray3d, Vec2, Vec3};
+use bevy_mesh::{Mesh, Mesh2d, Mesh3d, PrimitiveTopology, VertexAttributeValues};
 use bevy_picking::{
     Pickable,
     events::{Move, Out, Over, Pointer},
+    hover::HoverMap,
     mesh_picking::ray_cast::RayMeshHit,
+    pointer::{Location, PointerAction, PointerButton, PointerId, PointerInput},
     prelude::{MeshRayCast, MeshRayCastSettings, RayCastVisibility},
 };
+use bevy_platform::collections::HashMap;
 use bevy_transform::components::GlobalTransform;
 use bevy_window::PrimaryWindow;
 
@@ -42,43 +55,61 @@ pub fn handle_move_system(
 
     // Ray-cast attempting to find the context again.
     // TODO: track https://github.com/bevyengine/bevy/issues/19883 - once it's fixed, we can avoid the double-work with ray-casting again.
-    let Ok((context_camera, global_transform, render_target)) = egui_contexts.get(event.hit.camera)
-    else {
+    let Some((context, position)) = ray_cast_pickable_egui_context(
+        &event.pointer_location,
+        event.hit.camera,
+        &mut mesh_ray_cast,
+        &egui_contexts,
+        &pickable_egui_context_query,
+        &primary_window_query,
+    ) else {
         return Ok(());
     };
+
+    // The only thing we need to do here from the Egui context perspective is to update the `EguiContextPointerPosition` component.
+    // Other input systems will take care of the rest.
+    egui_pointers.get_mut(context)?.position = position;
+
+    Ok(())
+}
+
+/// Ray-casts meshes rendering pickable Egui contexts from the camera,
+/// returns the hit context and the pointer position in it.
+fn ray_cast_pickable_egui_context(
+    location: &Location,
+    camera: Entity,
+    mesh_ray_cast: &mut MeshRayCast,
+    egui_contexts: &Query<(&Camera, &GlobalTransform, &RenderTarget), With<EguiContext>>,
+    pickable_egui_context_query: &Query<&PickableEguiContext>,
+    primary_window_query: &Query<Entity, With<PrimaryWindow>>,
+) -> Option<(Entity, egui::Pos2)> {
+    let Ok((context_camera, global_transform, render_target)) = egui_contexts.get(camera) else {
+        return None;
+    };
     let settings = MeshRayCastSettings {
         visibility: RayCastVisibility::Any,
         filter: &|entity| pickable_egui_context_query.contains(entity),
         early_exit_test: &|_| true,
     };
-    let Some(ray) = make_ray(
-        &primary_window_query,
+    let ray = make_ray(
+        primary_window_query,
         context_camera,
         global_transform,
         render_target,
         &bevy_picking::pointer::PointerLocation {
-            location: Some(event.pointer_location.clone()),
+            location: Some(location.clone()),
         },
-    ) else {
-        return Ok(());
-    };
+    )?;
     let &[(hit_entity, RayMeshHit { uv: Some(uv), .. })] = mesh_ray_cast.cast_ray(ray, &settings)
     else {
-        return Ok(());
+        return None;
     };
 
     // At this point, we expect that the context exists, since we checked that with the ray cast filter.
-    let &PickableEguiContext(context) = pickable_egui_context_query.get(hit_entity)?;
-    let (egui_mesh_camera, _, _) = egui_contexts.get(context)?;
-
-    // The only thing we need to do here from the Egui context perspective is to update the `EguiContextPointerPosition` component.
-    // Other input systems will take care of the rest.
-    let Some(viewport_size) = egui_mesh_camera.logical_target_size() else {
-        return Ok(());
-    };
-    egui_pointers.get_mut(context)?.position = helpers::vec2_into_egui_pos2(viewport_size * uv);
-
-    Ok(())
+    let &PickableEguiContext(context) = pickable_egui_context_query.get(hit_entity).ok()?;
+    let (egui_mesh_camera, _, _) = egui_contexts.get(context).ok()?;
+    let viewport_size = egui_mesh_camera.logical_target_size()?;
+    Some((context, helpers::vec2_into_egui_pos2(viewport_size * uv)))
 }
 
 /// Inserts the [`HoveredNonWindowEguiContext`] resource containing the hovered Egui context.
@@ -108,6 +139,490 @@ pub fn handle_out_system(
     }
 }
 
+/// State of a [`bevy_picking`] pointer tracked by [`write_picking_pointer_messages_system`].
+#[derive(Default)]
+pub struct EguiPickingPointerState {
+    context: Option<Entity>,
+    position: egui::Pos2,
+    pressed_buttons: usize,
+}
+
+/// Reads [`PointerInput`] messages of all [`bevy_picking`] pointers (the mouse, touches and custom pointers)
+/// and wraps them into [`EguiInputEvent`]s, replacing the window pointer, touch and mouse wheel systems
+/// if [`EguiGlobalSettings::enable_picking_pointer_input`] is enabled.
+///
+/// A pointer operates the world-space context (see [`PickableEguiContext`]) it hovers,
+/// or the context rendering to the pointer's target with the highest camera order.
+/// While a button is pressed, the pointer keeps operating the context where the press has started.
+///
+/// Egui has a single pointer per context, which is owned by the pointer that has moved or pressed a button in it last,
+/// unless the owner holds a button. Touch pointers are also sent as [`egui::Event::Touch`] events.
+#[allow(clippy::too_many_arguments)]
+pub fn write_picking_pointer_messages_system(
+    egui_global_settings: Res<EguiGlobalSettings>,
+    mut commands: Commands,
+    modifier_keys_state: Res<ModifierKeysState>,
+    window_modifier_keys_state: Res<WindowModifierKeysState>,
+    mut pointer_input_reader: MessageReader<PointerInput>,
+    hover_map: Option<Res<HoverMap>>,
+    mut mesh_ray_cast: MeshRayCast,
+    ray_cast_egui_contexts: Query<(&Camera, &GlobalTransform, &RenderTarget), With<EguiContext>>,
+    mut egui_contexts: Query<
+        (
+            Entity,
+            &Camera,
+            &RenderTarget,
+            &EguiZoomFactor,
+            &EguiContextSettings,
+            &mut EguiContextPointerPosition,
+        ),
+        With<EguiContext>,
+    >,
+    pickable_egui_context_query: Query<&PickableEguiContext>,
+    primary_window_query: Query<Entity, With<PrimaryWindow>>,
+    mut pointers: Local<HashMap<PointerId, EguiPickingPointerState>>,
+    mut pointer_owners: Local<HashMap<Entity, PointerId>>,
+    mut egui_input_message_writer: MessageWriter<EguiInputEvent>,
+) {
+    let primary_window = primary_window_query.single().ok();
+
+    for PointerInput {
+        pointer_id,
+        location,
+        action,
+    } in pointer_input_reader.read()
+    {
+        let pointer_id = *pointer_id;
+        let modifiers = match &location.target {
+            NormalizedRenderTarget::Window(window) => {
+                window_modifier_keys_state.get(window.entity())
+            }
+            _ => &modifier_keys_state,
+        }
+        .to_egui_modifiers();
+        // World-space contexts hovered by the pointer take priority over the contexts rendering to its target.
+        let hovered_world_space_context = hover_map
+            .as_ref()
+            .and_then(|hover_map| hover_map.get(&pointer_id))
+            .into_iter()
+            .flatten()
+            .filter(|(entity, _)| pickable_egui_context_query.contains(**entity))
+            .find_map(|(_, hit)| {
+                ray_cast_pickable_egui_context(
+                    location,
+                    hit.camera,
+                    &mut mesh_ray_cast,
+                    &ray_cast_egui_contexts,
+                    &pickable_egui_context_query,
+                    &primary_window_query,
+                )
+            });
+        // Positions of the pointer in the contexts rendering to its target.
+        let target_position = |render_target: &RenderTarget, zoom_factor: &EguiZoomFactor| {
+            (render_target.normalize(primary_window).as_ref() == Some(&location.target))
+                .then(|| helpers::vec2_into_egui_pos2(location.position / zoom_factor.zoom_factor))
+        };
+        let hovered_context = hovered_world_space_context.or_else(|| {
+            egui_contexts
+                .iter()
+                .filter(|(_, camera, render_target, ..)| {
+                    location.is_in_viewport(camera, render_target, &primary_window_query)
+                })
+                .max_by_key(|(_, camera, ..)| camera.order)
+                .and_then(|(context, _, render_target, zoom_factor, ..)| {
+                    Some((context, target_position(render_target, zoom_factor)?))
+                })
+        });
+
+        let pointer = pointers.entry(pointer_id).or_default();
+        let target = if pointer.pressed_buttons > 0
+            && let Some(captured_context) = pointer.context
+        {
+            let position = match hovered_context {
+                Some((context, position)) if context == captured_context => Some(position),
+                _ => egui_contexts.get(captured_context).ok().and_then(
+                    |(_, _, render_target, zoom_factor, ..)| {
+                        target_position(render_target, zoom_factor)
+                    },
+                ),
+            };
+            Some((captured_context, position.unwrap_or(pointer.position)))
+        } else {
+            hovered_context
+        };
+
+        // The pointer has left the context it operated.
+        if let Some(previous_context) = pointer.context
+            && target.is_none_or(|(context, _)| context != previous_context)
+        {
+            pointer.context = None;
+            if pointer_owners.get(&previous_context) == Some(&pointer_id) {
+                pointer_owners.remove(&previous_context);
+                egui_input_message_writer.write(EguiInputEvent {
+                    context: previous_context,
+                    source: EguiInputSource::PickingPointer,
+                    event: egui::Event::PointerGone,
+                });
+            }
+        }
+        let Some((context, position)) = target else {
+            continue;
+        };
+        let Ok((.., context_settings, mut context_pointer_position)) =
+            egui_contexts.get_mut(context)
+        else {
+            continue;
+        };
+        if !context_settings
+            .input_system_settings
+            .run_write_picking_pointer_messages_system
+        {
+            continue;
+        }
+
+        // Another pointer is holding a button in this context.
+        if let Some(owner) = pointer_owners.get(&context)
+            && *owner != pointer_id
+            && pointers
+                .get(owner)
+                .is_some_and(|owner| owner.pressed_buttons > 0)
+        {
+            continue;
+        }
+        pointer_owners.insert(context, pointer_id);
+        let pointer = pointers.entry(pointer_id).or_default();
+        pointer.context = Some(context);
+        pointer.position = position;
+        context_pointer_position.position = position;
+
+        let mut write = |event| {
+            egui_input_message_writer.write(EguiInputEvent {
+                context,
+                source: EguiInputSource::PickingPointer,
+                event,
+            });
+        };
+        let touch_phase = match action {
+            PointerAction::Press(_) => Some(egui::TouchPhase::Start),
+            PointerAction::Move { .. } => Some(egui::TouchPhase::Move),
+            PointerAction::Release(_) => Some(egui::TouchPhase::End),
+            PointerAction::Cancel => Some(egui::TouchPhase::Cancel),
+            PointerAction::Scroll { .. } => None,
+        };
+        if let PointerId::Touch(id) = pointer_id
+            && let Some(phase) = touch_phase
+        {
+            write(egui::Event::Touch {
+                device_id: egui::TouchDeviceId(match location.target {
+                    NormalizedRenderTarget::Window(window) => window.entity().to_bits(),
+                    _ => 0,
+                }),
+                id: egui::TouchId::from(id),
+                phase,
+                pos: position,
+                force: None,
+            });
+        }
+
+        match *action {
+            PointerAction::Move { .. } => write(egui::Event::PointerMoved(position)),
+            PointerAction::Press(button) | PointerAction::Release(button) => {
+                let pressed = matches!(action, PointerAction::Press(_));
+                if pressed {
+                    pointer.pressed_buttons += 1;
+                    // Pressing a button focuses world-space contexts.
+                    if egui_global_settings.enable_focused_non_window_context_updates {
+                        if hovered_world_space_context.is_some() {
+                            commands.insert_resource(FocusedNonWindowEguiContext(context));
+                        } else {
+                            commands.remove_resource::<FocusedNonWindowEguiContext>();
+                        }
+                    }
+                    write(egui::Event::PointerMoved(position));
+                } else {
+                    pointer.pressed_buttons = pointer.pressed_buttons.saturating_sub(1);
+                }
+                write(egui::Event::PointerButton {
+                    pos: position,
+                    button: match button {
+                        PointerButton::Primary => egui::PointerButton::Primary,
+                        PointerButton::Secondary => egui::PointerButton::Secondary,
+                        PointerButton::Middle => egui::PointerButton::Middle,
+                    },
+                    pressed,
+                    modifiers,
+                });
+                // Touches don't hover after they end.
+                if !pressed && pointer_id.is_touch() {
+                    pointer.context = None;
+                    pointer_owners.remove(&context);
+                    write(egui::Event::PointerGone);
+                }
+            }
+            PointerAction::Scroll { unit, x, y, phase } => write(egui::Event::MouseWheel {
+                unit: match unit {
+                    MouseScrollUnit::Line => egui::MouseWheelUnit::Line,
+                    MouseScrollUnit::Pixel => egui::MouseWheelUnit::Point,
+                },
+                delta: egui::vec2(x, y),
+                phase: match phase {
+                    TouchPhase::Started => egui::TouchPhase::Start,
+                    TouchPhase::Moved => egui::TouchPhase::Move,
+                    TouchPhase::Ended => egui::TouchPhase::End,
+                    TouchPhase::Canceled => egui::TouchPhase::Cancel,
+                },
+                modifiers,
+            }),
+            PointerAction::Cancel => {
+                pointers.remove(&pointer_id);
+                pointer_owners.remove(&context);
+                write(egui::Event::PointerGone);
+            }
+        }
+    }
+
+    // Forget released touches, pointers that don't operate any context, and despawned contexts.
+    pointers.retain(|_, pointer| {
+        pointer
+            .context
+            .is_some_and(|context| egui_contexts.contains(context))
+    });
+    pointer_owners
+        .retain(|context, owner| egui_contexts.contains(*context) && pointers.contains_key(owner));
+}
+
+/// Places the [IME](https://en.wikipedia.org/wiki/Input_method) candidate window for world-space contexts
+/// (see [`PickableEguiContext`]), and allows IME in the window showing the [`FocusedNonWindowEguiContext`]
+/// while it has an active text edit. IME stays allowed in a window while any context shown in it needs it
+/// (see [`EguiWindowImeState`]).
+///
+/// Unlike window contexts handled by [`crate::input::process_ime_system`], the IME rect of a world-space context
+/// is mapped onto a mesh displaying the context by its UV coordinates, and projected through the highest-order
+/// active camera that renders the mesh to a window. [`EguiContextImeState::ime_rect`] stores the projected rect
+/// in logical window pixels.
+#[allow(clippy::too_many_arguments)]
+pub fn process_world_space_ime_system(
+    mut egui_contexts: Query<(
+        Entity,
+        &Camera,
+        &EguiZoomFactor,
+        &EguiOutput,
+        &EguiContextSettings,
+        &mut EguiContext,
+        &mut EguiContextImeState,
+    )>,
+    pickable_egui_context_query: Query<(
+        &PickableEguiContext,
+        Option<&Mesh2d>,
+        Option<&Mesh3d>,
+        &GlobalTransform,
+    )>,
+    cameras: Query<(&Camera, &GlobalTransform, &RenderTarget)>,
+    meshes: Res<Assets<Mesh>>,
+    focused_non_window_egui_context: Option<Res<FocusedNonWindowEguiContext>>,
+    window_to_egui_context_map: Res<WindowToEguiContextMap>,
+    primary_window_query: Query<Entity, With<PrimaryWindow>>,
+    mut window_ime_state: ResMut<EguiWindowImeState>,
+    _non_send_marker: NonSendMarker,
+) {
+    let primary_window = primary_window_query.single().ok();
+    let with_winit_window = |window: Entity, f: &dyn Fn(&winit::window::Window)| {
+        bevy_winit::WINIT_WINDOWS.with_borrow(|winit_windows| {
+            match winit_windows.get_window(window) {
+                Some(winit_window) => f(winit_window),
+                None => log::warn!(
+                    "Cannot access an underlying winit window for a window entity {}",
+                    window
+                ),
+            }
+        });
+    };
+
+    for (
+        context,
+        context_camera,
+        &EguiZoomFactor { zoom_factor },
+        egui_output,
+        egui_settings,
+        mut egui_context,
+        mut egui_ime_state,
+    ) in &mut egui_contexts
+    {
+        if window_to_egui_context_map
+            .context_to_window
+            .contains_key(&context)
+        {
+            continue;
+        }
+
+        let is_focused = focused_non_window_egui_context
+            .as_deref()
+            .is_some_and(|focused_context| focused_context.0 == context);
+        let target = egui_output
+            .platform_output
+            .ime
+            .filter(|_| egui_settings.enable_ime && is_focused)
+            .and_then(|ime| {
+                let texture_size = context_camera.logical_target_size()?;
+                let mesh_instances = pickable_egui_context_query.iter().filter_map(
+                    |(&PickableEguiContext(mesh_context), mesh_2d, mesh_3d, transform)| {
+                        let handle = mesh_3d
+                            .map(|mesh| &mesh.0)
+                            .or(mesh_2d.map(|mesh| &mesh.0))?;
+                        (mesh_context == context).then_some((meshes.get(handle)?, transform))
+                    },
+                );
+                project_world_space_rect(
+                    ime.rect * zoom_factor,
+                    texture_size,
+                    mesh_instances,
+                    &cameras,
+                    primary_window,
+                )
+            });
+
+        // Disallows IME in the window that the context is no longer shown in, unless other contexts need it.
+        window_ime_state.set_context_window(context, target.map(|(window, _)| window));
+        egui_ime_state.is_ime_allowed = target.is_some();
+
+        let Some((window, ime_rect)) = target else {
+            egui_ime_state.ime_rect = None;
+            continue;
+        };
+        if egui_ime_state.ime_rect != Some(ime_rect)
+            || egui_context.get_mut().input(|i| !i.events.is_empty())
+        {
+            egui_ime_state.ime_rect = Some(ime_rect);
+            with_winit_window(window, &|winit_window| {
+                winit_window.set_ime_cursor_area(
+                    winit::dpi::LogicalPosition {
+                        x: ime_rect.min.x,
+                        y: ime_rect.min.y,
+                    },
+                    winit::dpi::LogicalSize {
+                        width: ime_rect.width(),
+                        height: ime_rect.height(),
+                    },
+                );
+            });
+        }
+    }
+    window_ime_state.retain_contexts(|context| egui_contexts.contains(context));
+}
+
+/// Projects a rect of a world-space context texture (in logical pixels) onto the meshes displaying it,
+/// returns the window of the highest-order camera seeing the projected rect and the rect in logical window pixels.
+fn project_world_space_rect<'a>(
+    rect: egui::Rect,
+    texture_size: Vec2,
+    mesh_instances: impl Iterator<Item = (&'a Mesh, &'a GlobalTransform)>,
+    cameras: &Query<(&Camera, &GlobalTransform, &RenderTarget)>,
+    primary_window: Option<Entity>,
+) -> Option<(Entity, egui::Rect)> {
+    let mut result: Option<(isize, Entity, egui::Rect)> = None;
+    for (mesh, mesh_transform) in mesh_instances {
+        let Some(corners) = [
+            rect.left_top(),
+            rect.right_top(),
+            rect.left_bottom(),
+            rect.right_bottom(),
+        ]
+        .into_iter()
+        .map(|corner| {
+            let uv = helpers::egui_pos2_into_vec2(corner) / texture_size;
+            mesh_uv_to_local_position(mesh, uv)
+                .map(|position| mesh_transform.transform_point(position))
+        })
+        .collect::<Option<Vec<_>>>() else {
+            continue;
+        };
+
+        for (camera, camera_transform, render_target) in cameras.iter() {
+            let Some(NormalizedRenderTarget::Window(window)) =
+                render_target.normalize(primary_window)
+            else {
+                continue;
+            };
+            if !camera.is_active || result.is_some_and(|(order, ..)| order >= camera.order) {
+                continue;
+            }
+            let Some(projected_rect) = corners
+                .iter()
+                .map(|&corner| {
+                    camera
+                        .world_to_viewport(camera_transform, corner)
+                        .ok()
+                        .map(helpers::vec2_into_egui_pos2)
+                })
+                .collect::<Option<Vec<_>>>()
+                .map(|points| egui::Rect::from_points(&points))
+            else {
+                continue;
+            };
+            let Some(viewport_rect) = camera.logical_viewport_rect() else {
+                continue;
+            };
+            let viewport_rect = egui::Rect::from_min_max(
+                helpers::vec2_into_egui_pos2(viewport_rect.min),
+                helpers::vec2_into_egui_pos2(viewport_rect.max),
+            );
+            if viewport_rect.intersects(projected_rect) {
+                result = Some((camera.order, window.entity(), projected_rect));
+            }
+        }
+    }
+    result.map(|(_, window, rect)| (window, rect))
+}
+
+/// Finds a point of a triangle list mesh by its UV coordinates, returns its position in the mesh space.
+fn mesh_uv_to_local_position(mesh: &Mesh, uv: Vec2) -> Option<Vec3> {
+    // Allow points on triangle edges despite rounding errors.
+    const EPSILON: f32 = 1e-5;
+
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return None;
+    }
+    let positions = mesh
+        .try_attribute_option(Mesh::ATTRIBUTE_POSITION)
+        .ok()??
+        .as_float3()?;
+    let Some(VertexAttributeValues::Float32x2(uvs)) =
+        mesh.try_attribute_option(Mesh::ATTRIBUTE_UV_0).ok()?
+    else {
+        return None;
+    };
+    let indices: Vec<usize> = match mesh.try_indices_option().ok()? {
+        Some(indices) => indices.iter().collect(),
+        None => (0..positions.len()).collect(),
+    };
+
+    indices.chunks_exact(3).find_map(|triangle| {
+        let [a, b, c] = [triangle[0], triangle[1], triangle[2]];
+        let uv_a = Vec2::from(*uvs.get(a)?);
+        let (ab, ac, ap) = (
+            Vec2::from(*uvs.get(b)?) - uv_a,
+            Vec2::from(*uvs.get(c)?) - uv_a,
+            uv - uv_a,
+        );
+        let area = ab.perp_dot(ac);
+        if area.abs() < f32::EPSILON {
+            return None;
+        }
+        // Barycentric coordinates of the point in the UV space.
+        let v = ap.perp_dot(ac) / area;
+        let w = ab.perp_dot(ap) / area;
+        let u = 1.0 - v - w;
+        if u < -EPSILON || v < -EPSILON || w < -EPSILON {
+            return None;
+        }
+        Some(
+            Vec3::from(*positions.get(a)?) * u
+                + Vec3::from(*positions.get(b)?) * v
+                + Vec3::from(*positions.get(c)?) * w,
+        )
+    })
+}
+
 fn make_ray(
     primary_window_entity: &Query<Entity, With<PrimaryWindow>>,
     camera: &Camera,
@@ -126,3 +641,224 @@ fn make_ray(
     }
     camera.viewport_to_world(camera_tfm, viewport_pos).ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::{App, Update};
+    use bevy_asset::uuid::Uuid;
+    use bevy_camera::{RenderTargetInfo, Viewport};
+    use bevy_ecs::{system::SystemState, world::World};
+    use bevy_math::{Mat4, UVec2, primitives::Rectangle};
+    use bevy_transform::components::Transform;
+    use bevy_window::{Window, WindowRef};
+
+    fn spawn_context(app: &mut App, order: isize, viewport: Option<Viewport>) -> Entity {
+        let mut camera = Camera {
+            order,
+            viewport,
+            ..Default::default()
+        };
+        camera.computed.target_info = Some(RenderTargetInfo {
+            physical_size: UVec2::new(800, 600),
+            scale_factor: 1.0,
+        });
+        app.world_mut()
+            .spawn((
+                EguiContext::default(),
+                camera,
+                RenderTarget::Window(WindowRef::Primary),
+            ))
+            .id()
+    }
+
+    fn send(app: &mut App, window: Entity, pointer_id: PointerId, x: f32, action: PointerAction) {
+        app.world_mut().write_message(PointerInput {
+            pointer_id,
+            location: Location {
+                target: RenderTarget::Window(WindowRef::Entity(window))
+                    .normalize(None)
+                    .unwrap(),
+                position: Vec2::new(x, 100.0),
+            },
+            action,
+        });
+    }
+
+    fn moved() -> PointerAction {
+        PointerAction::Move { delta: Vec2::ZERO }
+    }
+
+    fn events(app: &mut App) -> Vec<(Entity, egui::Event)> {
+        app.update();
+        app.world()
+            .resource::<bevy_ecs::message::Messages<EguiInputEvent>>()
+            .iter_current_update_messages()
+            .map(|message| (message.context, message.event.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn picking_pointers_drive_contexts() {
+        let mut app = App::new();
+        app.add_message::<PointerInput>()
+            .add_message::<EguiInputEvent>()
+            .insert_resource(EguiGlobalSettings {
+                enable_picking_pointer_input: true,
+                ..Default::default()
+            })
+            .init_resource::<ModifierKeysState>()
+            .init_resource::<WindowModifierKeysState>()
+            .init_resource::<Assets<Mesh>>()
+            .add_systems(Update, write_picking_pointer_messages_system);
+
+        let window = app.world_mut().spawn(PrimaryWindow).id();
+        let back = spawn_context(&mut app, 0, None);
+        // Covers the right half of the window.
+        let front = spawn_context(
+            &mut app,
+            1,
+            Some(Viewport {
+                physical_position: UVec2::new(400, 0),
+                physical_size: UVec2::new(400, 600),
+                ..Default::default()
+            }),
+        );
+        let mouse = PointerId::Mouse;
+        let custom = PointerId::Custom(Uuid::from_u128(1));
+
+        // Pointers drive the contexts with the highest order under them.
+        send(&mut app, window, mouse, 100.0, moved());
+        send(&mut app, window, custom, 500.0, moved());
+        assert_eq!(
+            events(&mut app),
+            vec![
+                (back, egui::Event::PointerMoved(egui::pos2(100.0, 100.0))),
+                (front, egui::Event::PointerMoved(egui::pos2(500.0, 100.0))),
+            ]
+        );
+
+        // A pointer holding a button keeps the egui pointer of its context.
+        let press = |x, pressed| egui::Event::PointerButton {
+            pos: egui::pos2(x, 100.0),
+            button: egui::PointerButton::Primary,
+            pressed,
+            modifiers: ModifierKeysState::default().to_egui_modifiers(),
+        };
+        send(
+            &mut app,
+            window,
+            mouse,
+            100.0,
+            PointerAction::Press(PointerButton::Primary),
+        );
+        send(&mut app, window, custom, 200.0, moved());
+        assert_eq!(
+            events(&mut app),
+            vec![
+                (back, egui::Event::PointerMoved(egui::pos2(100.0, 100.0))),
+                (back, press(100.0, true)),
+                (front, egui::Event::PointerGone),
+            ]
+        );
+
+        // The pressed pointer stays captured by its context.
+        send(&mut app, window, mouse, 600.0, moved());
+        send(
+            &mut app,
+            window,
+            mouse,
+            600.0,
+            PointerAction::Release(PointerButton::Primary),
+        );
+        assert_eq!(
+            events(&mut app),
+            vec![
+                (back, egui::Event::PointerMoved(egui::pos2(600.0, 100.0))),
+                (back, press(600.0, false)),
+            ]
+        );
+
+        // Once released, the last active pointer takes over.
+        send(&mut app, window, custom, 300.0, moved());
+        send(&mut app, window, mouse, 700.0, moved());
+        assert_eq!(
+            events(&mut app),
+            vec![
+                (back, egui::Event::PointerMoved(egui::pos2(300.0, 100.0))),
+                (front, egui::Event::PointerMoved(egui::pos2(700.0, 100.0))),
+            ]
+        );
+        assert_eq!(
+            app.world()
+                .get::<EguiContextPointerPosition>(back)
+                .unwrap()
+                .position,
+            egui::pos2(300.0, 100.0)
+        );
+    }
+
+    #[test]
+    fn world_space_rect_is_projected_through_mesh_uv() {
+        let mut world = World::new();
+        let window = world.spawn((Window::default(), PrimaryWindow)).id();
+        let mut spawn_camera = |order, is_active| {
+            let mut camera = Camera {
+                order,
+                is_active,
+                ..Default::default()
+            };
+            camera.computed.target_info = Some(RenderTargetInfo {
+                physical_size: UVec2::new(800, 600),
+                scale_factor: 1.0,
+            });
+            // One world unit is one pixel.
+            camera.computed.clip_from_view =
+                Mat4::orthographic_rh(-400.0, 400.0, -300.0, 300.0, 0.0, 1000.0);
+            world.spawn((
+                camera,
+                GlobalTransform::IDENTITY,
+                RenderTarget::Window(WindowRef::Primary),
+            ));
+        };
+        spawn_camera(0, true);
+        spawn_camera(1, false);
+        let mut cameras: SystemState<Query<(&Camera, &GlobalTransform, &RenderTarget)>> =
+            SystemState::new(&mut world);
+        let cameras = cameras.get(&world).unwrap();
+
+        // A 200x100 rectangle showing a 400x200 texture.
+        let mesh = Mesh::from(Rectangle::new(2.0, 1.0));
+        let mesh_transform = GlobalTransform::from(
+            Transform::from_xyz(0.0, 0.0, -10.0).with_scale(Vec3::splat(100.0)),
+        );
+        let project = |rect| {
+            project_world_space_rect(
+                rect,
+                Vec2::new(400.0, 200.0),
+                std::iter::once((&mesh, &mesh_transform)),
+                &cameras,
+                Some(window),
+            )
+        };
+
+        assert_eq!(
+            project(egui::Rect::from_min_max(
+                egui::pos2(0.0, 0.0),
+                egui::pos2(40.0, 20.0)
+            )),
+            Some((
+                window,
+                egui::Rect::from_min_max(egui::pos2(300.0, 250.0), egui::pos2(320.0, 260.0))
+            ))
+        );
+        // The rect isn't mapped if it's outside the texture.
+        assert_eq!(
+            project(egui::Rect::from_min_max(
+                egui::pos2(390.0, 0.0),
+                egui::pos2(410.0, 20.0)
+            )),
+            None
+        );
+    }
+}
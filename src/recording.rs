@@ -0,0 +1,718 @@
+use crate::{
+    EguiContext, EguiContextSettings, EguiGlobalSettings, EguiInput, EguiInputSystemSettings,
+    input::{
+        EguiFileDragAndDropMessage, EguiInputEvent, EguiInputSource, ModifierKeysState,
+        WindowModifierKeysState, WindowToEguiContextMap,
+    },
+};
+use bevy_app::AppExit;
+use bevy_ecs::prelude::*;
+use bevy_log as log;
+use bevy_platform::collections::HashMap;
+use bevy_time::{Real, Time};
+use bevy_window::FileDragAndDrop;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Input recorded by [`EguiInputRecorder`], one entry per app update.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EguiInputRecording {
+    /// Recorded frames.
+    pub frames: Vec<EguiRecordedFrame>,
+}
+
+/// Input of a single app update.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EguiRecordedFrame {
+    /// Time in seconds since the recording start.
+    pub time: f64,
+    /// State of the modifier keys, used by contexts that aren't attached to a window.
+    pub modifiers: EguiRecordedModifiers,
+    /// Input of every Egui context.
+    pub contexts: Vec<EguiRecordedContextInput>,
+}
+
+/// State of the modifier keys (see [`ModifierKeysState`]).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EguiRecordedModifiers {
+    /// Whether the Shift key is pressed.
+    pub shift: bool,
+    /// Whether the Control key is pressed.
+    pub ctrl: bool,
+    /// Whether the Alt key is pressed.
+    pub alt: bool,
+    /// Whether the Super (or Meta) key is pressed.
+    pub win: bool,
+}
+
+impl From<&ModifierKeysState> for EguiRecordedModifiers {
+    fn from(state: &ModifierKeysState) -> Self {
+        Self {
+            shift: state.shift,
+            ctrl: state.ctrl,
+            alt: state.alt,
+            win: state.win,
+        }
+    }
+}
+
+/// Input of a single Egui context.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EguiRecordedContextInput {
+    /// Context entity (see [`Entity::to_bits`]), can be remapped with [`EguiInputReplayer::context_map`].
+    pub context: u64,
+    /// Screen rect of the context.
+    pub screen_rect: Option<egui::Rect>,
+    /// State of the modifier keys of the context's window (see [`WindowModifierKeysState`]).
+    #[serde(default)]
+    pub modifiers: EguiRecordedModifiers,
+    /// Events sent with [`EguiInputEvent`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<EguiRecordedEvent>,
+    /// File drag-and-drop events sent with [`EguiFileDragAndDropMessage`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub file_drag_and_drop: Vec<EguiRecordedFileDragAndDrop>,
+}
+
+/// A recorded [`EguiInputEvent`] message.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EguiRecordedEvent {
+    /// System that has written the message.
+    pub source: EguiInputSource,
+    /// Recorded event.
+    pub event: egui::Event,
+}
+
+/// A recorded [`FileDragAndDrop`] message.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EguiRecordedFileDragAndDrop {
+    /// See [`FileDragAndDrop::DroppedFile`].
+    DroppedFile(PathBuf),
+    /// See [`FileDragAndDrop::HoveredFile`].
+    HoveredFile(PathBuf),
+    /// See [`FileDragAndDrop::HoveredFileCanceled`].
+    HoveredFileCanceled,
+}
+
+/// An error that can happen when saving or loading an [`EguiInputRecording`].
+#[derive(Debug)]
+pub enum EguiInputRecordingError {
+    /// Failed to read or write a file.
+    Io(std::io::Error),
+    /// Failed to serialize a recording.
+    Serialize(ron::Error),
+    /// Failed to deserialize a recording.
+    Deserialize(ron::de::SpannedError),
+}
+
+impl std::fmt::Display for EguiInputRecordingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to access an Egui input recording file: {err}"),
+            Self::Serialize(err) => write!(f, "failed to serialize an Egui input recording: {err}"),
+            Self::Deserialize(err) => {
+                write!(f, "failed to deserialize an Egui input recording: {err}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EguiInputRecordingError {}
+
+impl EguiInputRecording {
+    /// Serializes the recording into a RON string.
+    pub fn to_ron_string(&self) -> Result<String, EguiInputRecordingError> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(EguiInputRecordingError::Serialize)
+    }
+
+    /// Deserializes a recording from a RON string.
+    pub fn from_ron_str(s: &str) -> Result<Self, EguiInputRecordingError> {
+        ron::from_str(s).map_err(EguiInputRecordingError::Deserialize)
+    }
+
+    /// Writes the recording to a RON file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), EguiInputRecordingError> {
+        std::fs::write(path, self.to_ron_string()?).map_err(EguiInputRecordingError::Io)
+    }
+
+    /// Reads a recording from a RON file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, EguiInputRecordingError> {
+        Self::from_ron_str(&std::fs::read_to_string(path).map_err(EguiInputRecordingError::Io)?)
+    }
+}
+
+/// Records [`EguiInputEvent`] and [`EguiFileDragAndDropMessage`] messages, together with modifier keys state
+/// and screen rects of Egui contexts, to be replayed with [`EguiInputReplayer`].
+///
+/// Only the events written by input systems (see [`EguiInputSource::is_input_system`]) are recorded,
+/// as the other ones (e.g. the ones written by users) are still written during the replay.
+///
+/// Call [`EguiInputRecorder::start`] to start recording, and [`EguiInputRecorder::stop`] to write the recording to a file.
+/// An active recording is also saved on [`AppExit`].
+#[derive(Resource, Default)]
+pub struct EguiInputRecorder {
+    path: Option<PathBuf>,
+    start_time: Option<f64>,
+    recording: EguiInputRecording,
+}
+
+impl EguiInputRecorder {
+    /// Starts a new recording that will be written to `path`.
+    pub fn start(&mut self, path: impl Into<PathBuf>) {
+        self.path = Some(path.into());
+        self.start_time = None;
+        self.recording = EguiInputRecording::default();
+    }
+
+    /// Returns `true` if the recording is in progress.
+    pub fn is_recording(&self) -> bool {
+        self.path.is_some()
+    }
+
+    /// Returns the current (or the last finished) recording.
+    pub fn recording(&self) -> &EguiInputRecording {
+        &self.recording
+    }
+
+    /// Stops recording and writes the recording to the file passed to [`EguiInputRecorder::start`].
+    pub fn stop(&mut self) -> Result<(), EguiInputRecordingError> {
+        match self.path.take() {
+            Some(path) => self.recording.save(path),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Replays an [`EguiInputRecording`], one recorded frame per app update.
+///
+/// While the resource exists, the input systems (see [`EguiInputSystemSettings`]) are disabled in [`EguiGlobalSettings`],
+/// and the recorded messages are fed to [`crate::input::write_egui_input_system`] instead.
+/// Recorded time (offset by the replay start time) and screen rects override the live ones, which makes the replay deterministic.
+/// Recorded events are skipped for contexts which [`EguiContextSettings::input_system_settings`] disable the systems
+/// that have written them (see [`EguiRecordedEvent::source`]).
+/// The settings are restored once the replay is finished.
+#[derive(Resource)]
+pub struct EguiInputReplayer {
+    /// Maps recorded context entities (see [`Entity::to_bits`]) to the live ones.
+    /// Recorded entities are used as-is if they aren't present in the map.
+    pub context_map: HashMap<u64, Entity>,
+    recording: EguiInputRecording,
+    next_frame: usize,
+    start_time: Option<f64>,
+    saved_input_system_settings: Option<EguiInputSystemSettings>,
+}
+
+impl EguiInputReplayer {
+    /// Creates a replayer for the recording.
+    pub fn new(recording: EguiInputRecording) -> Self {
+        Self {
+            context_map: HashMap::default(),
+            recording,
+            next_frame: 0,
+            start_time: None,
+            saved_input_system_settings: None,
+        }
+    }
+
+    /// Creates a replayer for the recording stored in a RON file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, EguiInputRecordingError> {
+        EguiInputRecording::load(path).map(Self::new)
+    }
+
+    /// Returns `true` if all the recorded frames have been replayed.
+    pub fn is_finished(&self) -> bool {
+        self.next_frame >= self.recording.frames.len()
+    }
+
+    fn context(&self, recorded: u64) -> Option<Entity> {
+        self.context_map
+            .get(&recorded)
+            .copied()
+            .or_else(|| Entity::try_from_bits(recorded))
+    }
+
+    fn current_frame(&self) -> Option<&EguiRecordedFrame> {
+        self.next_frame
+            .checked_sub(1)
+            .and_then(|index| self.recording.frames.get(index))
+    }
+}
+
+/// Records the input that was sent to Egui contexts during the current update.
+#[allow(clippy::too_many_arguments)]
+pub fn record_egui_input_system(
+    mut recorder: ResMut<EguiInputRecorder>,
+    mut egui_input_reader: MessageReader<EguiInputEvent>,
+    mut egui_file_dnd_message_reader: MessageReader<EguiFileDragAndDropMessage>,
+    modifier_keys_state: Res<ModifierKeysState>,
+    window_modifier_keys_state: Res<WindowModifierKeysState>,
+    map: Res<WindowToEguiContextMap>,
+    contexts: Query<(Entity, &EguiInput), With<EguiContext>>,
+    time: Res<Time<Real>>,
+) {
+    if !recorder.is_recording() {
+        egui_input_reader.clear();
+        egui_file_dnd_message_reader.clear();
+        return;
+    }
+
+    let mut inputs: Vec<EguiRecordedContextInput> = contexts
+        .iter()
+        .map(|(entity, egui_input)| EguiRecordedContextInput {
+            context: entity.to_bits(),
+            screen_rect: egui_input.screen_rect,
+            modifiers: match map.context_to_window.get(&entity) {
+                Some(window) => window_modifier_keys_state.get(*window).into(),
+                None => modifier_keys_state.as_ref().into(),
+            },
+            ..Default::default()
+        })
+        .collect();
+    for EguiInputEvent {
+        context,
+        source,
+        event,
+    } in egui_input_reader.read()
+    {
+        if !source.is_input_system() {
+            continue;
+        }
+        if let Some(input) = inputs.iter_mut().find(|i| i.context == context.to_bits()) {
+            input.events.push(EguiRecordedEvent {
+                source: *source,
+                event: event.clone(),
+            });
+        }
+    }
+    for EguiFileDragAndDropMessage { context, message } in egui_file_dnd_message_reader.read() {
+        if let Some(input) = inputs.iter_mut().find(|i| i.context == context.to_bits()) {
+            input.file_drag_and_drop.push(match message {
+                FileDragAndDrop::DroppedFile { path_buf, .. } => {
+                    EguiRecordedFileDragAndDrop::DroppedFile(path_buf.clone())
+                }
+                FileDragAndDrop::HoveredFile { path_buf, .. } => {
+                    EguiRecordedFileDragAndDrop::HoveredFile(path_buf.clone())
+                }
+                FileDragAndDrop::HoveredFileCanceled { .. } => {
+                    EguiRecordedFileDragAndDrop::HoveredFileCanceled
+                }
+            });
+        }
+    }
+
+    let elapsed = time.elapsed_secs_f64();
+    let start_time = *recorder.start_time.get_or_insert(elapsed);
+    recorder.recording.frames.push(EguiRecordedFrame {
+        time: elapsed - start_time,
+        modifiers: modifier_keys_state.as_ref().into(),
+        contexts: inputs,
+    });
+}
+
+/// Writes the active recording to a file on [`AppExit`].
+pub fn save_input_recording_on_exit_system(
+    mut app_exit_reader: MessageReader<AppExit>,
+    mut recorder: ResMut<EguiInputRecorder>,
+) {
+    if app_exit_reader.read().last().is_some()
+        && let Err(err) = recorder.stop()
+    {
+        log::error!("Failed to save the Egui input recording: {err}");
+    }
+}
+
+/// Feeds the next recorded frame into [`EguiInputEvent`] and [`EguiFileDragAndDropMessage`] messages,
+/// [`ModifierKeysState`] and the [`WindowModifierKeysState`] of the windows of the recorded contexts.
+#[allow(clippy::too_many_arguments)]
+pub fn replay_egui_input_system(
+    mut commands: Commands,
+    mut replayer: ResMut<EguiInputReplayer>,
+    mut egui_global_settings: ResMut<EguiGlobalSettings>,
+    mut modifier_keys_state: ResMut<ModifierKeysState>,
+    mut window_modifier_keys_state: ResMut<WindowModifierKeysState>,
+    map: Res<WindowToEguiContextMap>,
+    context_settings: Query<&EguiContextSettings>,
+    mut egui_input_message_writer: MessageWriter<EguiInputEvent>,
+    mut egui_file_dnd_message_writer: MessageWriter<EguiFileDragAndDropMessage>,
+) {
+    if replayer.saved_input_system_settings.is_none() {
+        let settings = &mut egui_global_settings.input_system_settings;
+        replayer.saved_input_system_settings = Some(settings.clone());
+        *settings = disabled_input_system_settings(settings);
+    }
+
+    if replayer.is_finished() {
+        if let Some(settings) = replayer.saved_input_system_settings.take() {
+            egui_global_settings.input_system_settings = settings;
+        }
+        commands.remove_resource::<EguiInputReplayer>();
+        return;
+    }
+
+    replayer.next_frame += 1;
+    let Some(frame) = replayer.current_frame() else {
+        return;
+    };
+
+    apply_recorded_modifiers(&mut modifier_keys_state, frame.modifiers);
+
+    for input in &frame.contexts {
+        let Some(context) = replayer.context(input.context) else {
+            continue;
+        };
+        let window = map.context_to_window.get(&context).copied();
+        if let Some(window) = window {
+            apply_recorded_modifiers(window_modifier_keys_state.get_mut(window), input.modifiers);
+        }
+        let settings = context_settings
+            .get(context)
+            .ok()
+            .map(|settings| &settings.input_system_settings);
+        for EguiRecordedEvent { source, event } in input
+            .events
+            .iter()
+            .filter(|event| settings.is_none_or(|settings| event.source.is_enabled(settings)))
+        {
+            egui_input_message_writer.write(EguiInputEvent {
+                context,
+                source: *source,
+                event: event.clone(),
+            });
+        }
+        let window = window.unwrap_or(Entity::PLACEHOLDER);
+        if settings.is_some_and(|settings| !settings.run_write_file_dnd_messages_system) {
+            continue;
+        }
+        for message in &input.file_drag_and_drop {
+            egui_file_dnd_message_writer.write(EguiFileDragAndDropMessage {
+                context,
+                message: match message {
+                    EguiRecordedFileDragAndDrop::DroppedFile(path_buf) => {
+                        FileDragAndDrop::DroppedFile {
+                            window,
+                            path_buf: path_buf.clone(),
+                        }
+                    }
+                    EguiRecordedFileDragAndDrop::HoveredFile(path_buf) => {
+                        FileDragAndDrop::HoveredFile {
+                            window,
+                            path_buf: path_buf.clone(),
+                        }
+                    }
+                    EguiRecordedFileDragAndDrop::HoveredFileCanceled => {
+                        FileDragAndDrop::HoveredFileCanceled { window }
+                    }
+                },
+            });
+        }
+    }
+}
+
+/// Overrides time and screen rects of Egui contexts with the values of the currently replayed frame.
+pub fn apply_replayed_frame_system(
+    mut replayer: ResMut<EguiInputReplayer>,
+    mut contexts: Query<&mut EguiInput, With<EguiContext>>,
+    time: Res<Time<Real>>,
+) {
+    if replayer.current_frame().is_none() {
+        return;
+    }
+    // Recorded time starts from zero, offsetting it keeps `egui::RawInput::time` monotonic.
+    let start_time = *replayer.start_time.get_or_insert(time.elapsed_secs_f64());
+    let Some(frame) = replayer.current_frame() else {
+        return;
+    };
+
+    for input in &frame.contexts {
+        let Some(mut egui_input) = replayer
+            .context(input.context)
+            .and_then(|context| contexts.get_mut(context).ok())
+        else {
+            continue;
+        };
+        egui_input.time = Some(start_time + frame.time);
+        if input.screen_rect.is_some() {
+            egui_input.screen_rect = input.screen_rect;
+        }
+    }
+}
+
+fn disabled_input_system_settings(settings: &EguiInputSystemSettings) -> EguiInputSystemSettings {
+    EguiInputSystemSettings {
+        // Not an input system: it reflects the state of windows.
+        run_write_viewport_info_system: settings.run_write_viewport_info_system,
+        ..EguiInputSystemSettings::all_disabled()
+    }
+}
+
+fn apply_recorded_modifiers(state: &mut ModifierKeysState, modifiers: EguiRecordedModifiers) {
+    let EguiRecordedModifiers {
+        shift,
+        ctrl,
+        alt,
+        win,
+    } = modifiers;
+    state.shift = shift;
+    state.ctrl = ctrl;
+    state.alt = alt;
+    state.win = win;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::write_egui_input_system;
+    use bevy_app::{App, Update};
+    use bevy_camera::Camera;
+    use bevy_window::WindowThemeChanged;
+
+    fn app_with_egui_input_system() -> App {
+        let mut app = App::new();
+        app.add_message::<EguiInputEvent>()
+            .add_message::<EguiFileDragAndDropMessage>()
+            .add_message::<WindowThemeChanged>()
+            .init_resource::<EguiGlobalSettings>()
+            .init_resource::<ModifierKeysState>()
+            .init_resource::<WindowModifierKeysState>()
+            .init_resource::<WindowToEguiContextMap>()
+            .init_resource::<Time<Real>>();
+        app
+    }
+
+    #[test]
+    fn recorded_input_is_replayed() {
+        let path = std::env::temp_dir().join(format!(
+            "bevy_egui_input_recording_{}.ron",
+            std::process::id()
+        ));
+        let screen_rect = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(320.0, 240.0));
+
+        let mut app = app_with_egui_input_system();
+        app.init_resource::<EguiInputRecorder>().add_systems(
+            Update,
+            (write_egui_input_system, record_egui_input_system).chain(),
+        );
+        let context = app
+            .world_mut()
+            .spawn((EguiContext::default(), Camera::default()))
+            .id();
+        app.world_mut()
+            .get_mut::<EguiInput>(context)
+            .unwrap()
+            .screen_rect = Some(screen_rect);
+        app.world_mut()
+            .resource_mut::<EguiInputRecorder>()
+            .start(&path);
+        app.world_mut().resource_mut::<ModifierKeysState>().shift = true;
+        app.world_mut().write_message(EguiInputEvent {
+            context,
+            source: EguiInputSource::KeyboardInput,
+            event: egui::Event::Text("A".to_owned()),
+        });
+        // Events that aren't written by input systems are still written during the replay.
+        app.world_mut().write_message(EguiInputEvent {
+            context,
+            source: EguiInputSource::Custom,
+            event: egui::Event::Text("B".to_owned()),
+        });
+        app.update();
+        app.world_mut().resource_mut::<ModifierKeysState>().shift = false;
+        app.world_mut().write_message(EguiFileDragAndDropMessage {
+            context,
+            message: FileDragAndDrop::DroppedFile {
+                window: Entity::PLACEHOLDER,
+                path_buf: PathBuf::from("dropped.txt"),
+            },
+        });
+        app.update();
+        app.world_mut()
+            .resource_mut::<EguiInputRecorder>()
+            .stop()
+            .unwrap();
+
+        let mut app = app_with_egui_input_system();
+        app.add_systems(
+            Update,
+            (
+                replay_egui_input_system,
+                write_egui_input_system,
+                apply_replayed_frame_system,
+            )
+                .chain()
+                .distributive_run_if(resource_exists::<EguiInputReplayer>),
+        );
+        app.world_mut().spawn_empty();
+        let replay_context = app
+            .world_mut()
+            .spawn((EguiContext::default(), Camera::default()))
+            .id();
+        let mut replayer = EguiInputReplayer::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        replayer
+            .context_map
+            .insert(context.to_bits(), replay_context);
+        app.insert_resource(replayer);
+        // The replay starts later than the recording did.
+        app.world_mut()
+            .resource_mut::<Time<Real>>()
+            .advance_by(std::time::Duration::from_secs(5));
+
+        let take_input = |app: &mut App| {
+            app.world_mut()
+                .get_mut::<EguiInput>(replay_context)
+                .unwrap()
+                .take()
+        };
+        app.update();
+        assert!(
+            !app.world()
+                .resource::<EguiGlobalSettings>()
+                .input_system_settings
+                .run_write_keyboard_input_messages_system
+        );
+        let input = take_input(&mut app);
+        assert_eq!(input.screen_rect, Some(screen_rect));
+        assert_eq!(input.time, Some(5.0));
+        assert_eq!(
+            input.events,
+            [
+                egui::Event::Text("A".to_owned()),
+                egui::Event::ModifiersChanged(egui::Modifiers::SHIFT),
+            ]
+        );
+
+        app.update();
+        let input = take_input(&mut app);
+        assert_eq!(input.dropped_files.len(), 1);
+        assert_eq!(
+            input.events,
+            [egui::Event::ModifiersChanged(egui::Modifiers::NONE)]
+        );
+
+        // The live input systems are re-enabled once the replay is finished.
+        app.update();
+        assert!(!app.world().contains_resource::<EguiInputReplayer>());
+        assert_eq!(
+            app.world()
+                .resource::<EguiGlobalSettings>()
+                .input_system_settings,
+            EguiInputSystemSettings::default()
+        );
+    }
+
+    #[test]
+    fn per_window_modifiers_are_replayed() {
+        let mut app = app_with_egui_input_system();
+        app.init_resource::<EguiInputRecorder>()
+            .add_systems(Update, record_egui_input_system);
+        let window = app.world_mut().spawn_empty().id();
+        let other_window = app.world_mut().spawn_empty().id();
+        let context = app.world_mut().spawn(EguiContext::default()).id();
+        let other_context = app.world_mut().spawn(EguiContext::default()).id();
+        let mut map = app.world_mut().resource_mut::<WindowToEguiContextMap>();
+        for (window, context) in [(window, context), (other_window, other_context)] {
+            map.window_to_contexts
+                .entry(window)
+                .or_default()
+                .insert(context);
+            map.context_to_window.insert(context, window);
+        }
+        app.world_mut()
+            .resource_mut::<EguiInputRecorder>()
+            .start(std::env::temp_dir().join("bevy_egui_unsaved_input_recording.ron"));
+        app.world_mut()
+            .resource_mut::<WindowModifierKeysState>()
+            .get_mut(window)
+            .ctrl = true;
+        app.update();
+        let recording = app
+            .world_mut()
+            .resource_mut::<EguiInputRecorder>()
+            .recording()
+            .clone();
+
+        let mut app = app_with_egui_input_system();
+        app.add_systems(Update, replay_egui_input_system);
+        let mut map = app.world_mut().resource_mut::<WindowToEguiContextMap>();
+        for (window, context) in [(window, context), (other_window, other_context)] {
+            map.window_to_contexts
+                .entry(window)
+                .or_default()
+                .insert(context);
+            map.context_to_window.insert(context, window);
+        }
+        app.world_mut()
+            .resource_mut::<WindowModifierKeysState>()
+            .get_mut(other_window)
+            .ctrl = true;
+        app.insert_resource(EguiInputReplayer::new(recording));
+        app.update();
+        let window_modifier_keys_state = app.world().resource::<WindowModifierKeysState>();
+        assert!(window_modifier_keys_state.get(window).ctrl);
+        assert!(!window_modifier_keys_state.get(other_window).ctrl);
+        assert!(!app.world().resource::<ModifierKeysState>().ctrl);
+    }
+
+    #[test]
+    fn replay_respects_context_input_system_settings() {
+        let mut app = app_with_egui_input_system();
+        app.add_systems(
+            Update,
+            (replay_egui_input_system, write_egui_input_system).chain(),
+        );
+        let context = app
+            .world_mut()
+            .spawn((
+                EguiContext::default(),
+                Camera::default(),
+                EguiContextSettings {
+                    input_system_settings: EguiInputSystemSettings {
+                        run_write_keyboard_input_messages_system: false,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            ))
+            .id();
+        app.insert_resource(EguiInputReplayer::new(EguiInputRecording {
+            frames: vec![EguiRecordedFrame {
+                contexts: vec![EguiRecordedContextInput {
+                    context: context.to_bits(),
+                    events: vec![
+                        EguiRecordedEvent {
+                            source: EguiInputSource::KeyboardInput,
+                            event: egui::Event::Text("A".to_owned()),
+                        },
+                        // Touches emit pointer events as well.
+                        EguiRecordedEvent {
+                            source: EguiInputSource::WindowTouch,
+                            event: egui::Event::PointerGone,
+                        },
+                        EguiRecordedEvent {
+                            source: EguiInputSource::Custom,
+                            event: egui::Event::Text("B".to_owned()),
+                        },
+                    ],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        }));
+
+        app.update();
+        let input = app
+            .world_mut()
+            .get_mut::<EguiInput>(context)
+            .unwrap()
+            .take();
+        assert_eq!(
+            input.events,
+            [
+                egui::Event::PointerGone,
+                egui::Event::Text("B".to_owned()),
+                egui::Event::ModifiersChanged(egui::Modifiers::NONE),
+            ]
+        );
+    }
+}
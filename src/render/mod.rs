@@ -4,7 +4,7 @@ use std::{
     num::{NonZero, NonZeroU32},
 };
 
-use crate::{EguiOutput, EguiPixelsPerPoint, EguiRenderOutput};
+use crate::{EguiOutput, EguiPixelsPerPoint, EguiRenderOutput, viewport::EguiViewport};
 use bevy_asset::{Handle, RenderAssetUsages, uuid_handle};
 use bevy_camera::{Camera, Hdr};
 use bevy_ecs::{
@@ -29,7 +29,7 @@ use bevy_render::{
         binding_types::{sampler, texture_2d, uniform_buffer},
     },
     renderer::{RenderContext, RenderDevice},
-    sync_world::{RenderEntity, TemporaryRenderEntity},
+    sync_world::{MainEntity, RenderEntity, TemporaryRenderEntity},
     view::{ExtractedView, RetainedViewEntity},
 };
 use bevy_shader::{Shader, ShaderDefVal};
@@ -72,6 +72,13 @@ pub struct EguiCameraView(pub Entity);
 #[derive(Component, Debug)]
 pub struct EguiViewTarget(pub Entity);
 
+/// A render-world component that lives on the Egui view and specifies the context entity
+/// which Egui-managed textures the view uses (see [`crate::EguiManagedTextures`]).
+///
+/// Native viewports (see [`EguiViewport`]) use the textures of their root context.
+#[derive(Component, Debug)]
+pub struct EguiTexturesContext(pub MainEntity);
+
 /// Extracts all Egui contexts associated with a camera into the render world.
 pub fn extract_egui_camera_view_system(
     mut commands: Commands,
@@ -85,6 +92,7 @@ pub fn extract_egui_camera_view_system(
         &Camera,
         &EguiOutput,
         &mut EguiRenderOutput,
+        Option<&EguiViewport>,
         Has<Hdr>,
         Has<Disabled>,
     )>();
@@ -95,6 +103,7 @@ pub fn extract_egui_camera_view_system(
         camera,
         egui_output,
         mut egui_render_output,
+        viewport,
         hdr,
         is_disabled,
     ) in &mut q.iter_mut(&mut world)
@@ -156,6 +165,9 @@ pub fn extract_egui_camera_view_system(
                     },
                     // Link to the main camera view.
                     EguiViewTarget(render_entity),
+                    EguiTexturesContext(MainEntity::from(
+                        viewport.map_or(main_entity, |viewport| viewport.root_context),
+                    )),
                     egui_render_output,
                     EguiPixelsPerPoint { pixels_per_point },
                     TemporaryRenderEntity,
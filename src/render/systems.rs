@@ -3,7 +3,7 @@ use crate::{
     EguiUserTextures,
     render::{
         DrawCommand, DrawPrimitive, EguiBevyPaintCallback, EguiCameraView, EguiDraw, EguiPipeline,
-        EguiPipelineKey, EguiViewTarget, PaintCallbackDraw,
+        EguiPipelineKey, EguiTexturesContext, EguiViewTarget, PaintCallbackDraw,
     },
 };
 use bevy_asset::prelude::*;
@@ -342,6 +342,7 @@ pub fn prepare_egui_render_target_data_system(
         &ExtractedView,
         &EguiPixelsPerPoint,
         &EguiViewTarget,
+        &EguiTexturesContext,
         &EguiRenderOutput,
     )>,
     extracted_cameras: Query<&ExtractedCamera>,
@@ -355,8 +356,14 @@ pub fn prepare_egui_render_target_data_system(
         keep
     });
 
-    for (render_entity, view, computed_scale_factor, egui_view_target, render_output) in
-        render_targets.iter()
+    for (
+        render_entity,
+        view,
+        computed_scale_factor,
+        egui_view_target,
+        &EguiTexturesContext(textures_context),
+        render_output,
+    ) in render_targets.iter()
     {
         let data = render_data
             .entry(view.retained_view_entity.main_entity)
@@ -461,9 +468,7 @@ pub fn prepare_egui_render_target_data_system(
             index_offset += mesh.vertices.len() as u32;
 
             let texture_handle = match mesh.texture_id {
-                egui::TextureId::Managed(id) => {
-                    EguiTextureId::Managed(view.retained_view_entity.main_entity, id)
-                }
+                egui::TextureId::Managed(id) => EguiTextureId::Managed(textures_context, id),
                 egui::TextureId::User(id) => EguiTextureId::User(id),
             };
 
@@ -0,0 +1,336 @@
+use crate::{
+    EguiContext, EguiInput,
+    input::{EguiContextWantsInput, WindowToEguiContextMap},
+};
+use bevy_ecs::{message::Message, prelude::*};
+
+/// Defines when a shortcut registered in [`EguiShortcuts`] fires.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EguiShortcutScope {
+    /// The shortcut fires for a key press sent to any context, even if Egui has keyboard focus.
+    Global,
+    /// The shortcut fires only for a key press sent to this context, even if Egui has keyboard focus.
+    Context(Entity),
+    /// The shortcut fires for a key press sent to any context, unless the context or any other context of its window
+    /// wants keyboard input (see [`crate::input::EguiWantsInput::wants_keyboard_input`]), e.g. when a user is typing in a text field.
+    NotTyping,
+}
+
+/// A shortcut registered in [`EguiShortcuts`].
+pub struct EguiShortcut {
+    /// Key and modifiers of the shortcut.
+    pub shortcut: egui::KeyboardShortcut,
+    /// When the shortcut fires.
+    pub scope: EguiShortcutScope,
+    write_message: Box<dyn Fn(&mut Commands) + Send + Sync>,
+}
+
+/// Keyboard shortcuts that emit Bevy messages, matched by [`write_shortcut_messages_system`].
+///
+/// Shortcuts are matched against the [`egui::Event::Key`] events built from [`bevy_input::keyboard::KeyboardInput`]
+/// the same way as with [`egui::InputState::consume_shortcut`]: extra Shift and Alt modifiers are ignored,
+/// and the matched key press is consumed, so that Egui widgets don't react to it.
+/// More specific shortcuts (with more modifiers) are matched first, so Ctrl+Shift+S doesn't trigger Ctrl+S.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_egui::{egui, shortcuts::{EguiShortcuts, EguiShortcutScope}};
+/// #[derive(Message, Clone)]
+/// struct Save;
+///
+/// #[derive(Message, Clone)]
+/// struct OpenCommandPalette;
+///
+/// fn setup_shortcuts(mut shortcuts: ResMut<EguiShortcuts>) {
+///     shortcuts
+///         .add(
+///             egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::S),
+///             EguiShortcutScope::Global,
+///             Save,
+///         )
+///         .add(
+///             egui::KeyboardShortcut::new(
+///                 egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
+///                 egui::Key::P,
+///             ),
+///             EguiShortcutScope::NotTyping,
+///             OpenCommandPalette,
+///         );
+/// }
+/// ```
+#[derive(Resource, Default)]
+pub struct EguiShortcuts {
+    shortcuts: Vec<EguiShortcut>,
+}
+
+impl EguiShortcuts {
+    /// Registers a shortcut that writes a clone of `message` every time it fires.
+    pub fn add<M: Message + Clone>(
+        &mut self,
+        shortcut: egui::KeyboardShortcut,
+        scope: EguiShortcutScope,
+        message: M,
+    ) -> &mut Self {
+        self.shortcuts.push(EguiShortcut {
+            shortcut,
+            scope,
+            write_message: Box::new(move |commands| {
+                commands.write_message(message.clone());
+            }),
+        });
+        // Keep the most specific shortcuts first (the sort is stable).
+        self.shortcuts
+            .sort_by_key(|shortcut| std::cmp::Reverse(modifier_count(shortcut.shortcut.modifiers)));
+        self
+    }
+
+    /// Removes all shortcuts with the given key and modifiers.
+    pub fn remove(&mut self, shortcut: egui::KeyboardShortcut) {
+        self.shortcuts
+            .retain(|registered| registered.shortcut != shortcut);
+    }
+
+    /// Iterates over the registered shortcuts.
+    pub fn iter(&self) -> impl Iterator<Item = &EguiShortcut> {
+        self.shortcuts.iter()
+    }
+}
+
+fn modifier_count(modifiers: egui::Modifiers) -> usize {
+    [
+        modifiers.alt,
+        modifiers.ctrl || modifiers.command,
+        modifiers.shift,
+        modifiers.mac_cmd,
+    ]
+    .into_iter()
+    .filter(|pressed| *pressed)
+    .count()
+}
+
+/// Matches key presses in [`EguiInput`] against [`EguiShortcuts`], removes the matched events
+/// and writes the messages of the fired shortcuts.
+///
+/// As the same key press is sent to all the contexts of a window, [`EguiShortcutScope::Global`]
+/// and [`EguiShortcutScope::NotTyping`] shortcuts fire at most once per update.
+pub fn write_shortcut_messages_system(
+    mut commands: Commands,
+    shortcuts: Res<EguiShortcuts>,
+    context_to_window: Res<WindowToEguiContextMap>,
+    mut egui_contexts: Query<(Entity, &mut EguiInput, &EguiContextWantsInput), With<EguiContext>>,
+    mut fired: Local<Vec<bool>>,
+    mut typing_windows: Local<bevy_platform::collections::HashSet<Entity>>,
+) {
+    if shortcuts.shortcuts.is_empty() {
+        return;
+    }
+
+    fired.clear();
+    fired.resize(shortcuts.shortcuts.len(), false);
+    // A window counts as typing if any of its contexts wants keyboard input.
+    typing_windows.clear();
+    typing_windows.extend(
+        egui_contexts
+            .iter()
+            .filter(|(_, _, wants_input)| wants_input.wants_keyboard_input())
+            .filter_map(|(context, _, _)| context_to_window.context_to_window.get(&context))
+            .copied(),
+    );
+    for (context, mut egui_input, wants_input) in &mut egui_contexts {
+        let is_typing = wants_input.wants_keyboard_input()
+            || context_to_window
+                .context_to_window
+                .get(&context)
+                .is_some_and(|window| typing_windows.contains(window));
+        egui_input.events.retain(|event| {
+            let egui::Event::Key {
+                key,
+                pressed: true,
+                repeat: false,
+                modifiers,
+                ..
+            } = event
+            else {
+                return true;
+            };
+
+            let matched = shortcuts.shortcuts.iter().position(|shortcut| {
+                let in_scope = match shortcut.scope {
+                    EguiShortcutScope::Global => true,
+                    EguiShortcutScope::Context(scope_context) => scope_context == context,
+                    EguiShortcutScope::NotTyping => !is_typing,
+                };
+                in_scope
+                    && shortcut.shortcut.logical_key == *key
+                    && modifiers.matches_logically(shortcut.shortcut.modifiers)
+            });
+            let Some(index) = matched else {
+                return true;
+            };
+
+            let shortcut = &shortcuts.shortcuts[index];
+            if !fired[index] || matches!(shortcut.scope, EguiShortcutScope::Context(_)) {
+                fired[index] = true;
+                (shortcut.write_message)(&mut commands);
+            }
+            false
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{EguiWantsInput, write_egui_wants_input_system};
+    use bevy_app::{App, Update};
+    use bevy_ecs::message::Messages;
+
+    #[derive(Message, Clone)]
+    struct Save;
+
+    #[derive(Message, Clone)]
+    struct SaveAs;
+
+    #[derive(Message, Clone)]
+    struct OpenCommandPalette;
+
+    fn key(key: egui::Key, modifiers: egui::Modifiers) -> egui::Event {
+        egui::Event::Key {
+            key,
+            physical_key: Some(key),
+            pressed: true,
+            repeat: false,
+            modifiers,
+        }
+    }
+
+    fn message_count<M: Message>(app: &App) -> usize {
+        app.world()
+            .resource::<Messages<M>>()
+            .iter_current_update_messages()
+            .count()
+    }
+
+    #[test]
+    fn shortcuts_write_messages_in_their_scopes() {
+        let mut app = App::new();
+        app.add_message::<Save>()
+            .add_message::<SaveAs>()
+            .add_message::<OpenCommandPalette>()
+            .init_resource::<EguiShortcuts>()
+            .init_resource::<EguiWantsInput>()
+            .init_resource::<WindowToEguiContextMap>()
+            .add_systems(
+                Update,
+                (
+                    write_egui_wants_input_system,
+                    write_shortcut_messages_system,
+                )
+                    .chain(),
+            );
+        let context = app.world_mut().spawn(EguiContext::default()).id();
+        // A text field is focused in the second context.
+        let typing_context = app.world_mut().spawn(EguiContext::default()).id();
+        let mut egui_context = app
+            .world_mut()
+            .get_mut::<EguiContext>(typing_context)
+            .unwrap();
+        let mut output = egui_context.get_mut().run_ui(Default::default(), |ui| {
+            ui.text_edit_singleline(&mut String::new()).request_focus();
+        });
+        output.textures_delta.clear();
+        // The third context shares a window with the second one.
+        let window_context = app.world_mut().spawn(EguiContext::default()).id();
+        let window = app.world_mut().spawn_empty().id();
+        let mut context_map = app.world_mut().resource_mut::<WindowToEguiContextMap>();
+        for window_context in [typing_context, window_context] {
+            context_map
+                .window_to_contexts
+                .entry(window)
+                .or_default()
+                .insert(window_context);
+            context_map.context_to_window.insert(window_context, window);
+        }
+
+        let ctrl = egui::Modifiers::COMMAND;
+        app.world_mut()
+            .resource_mut::<EguiShortcuts>()
+            .add(
+                egui::KeyboardShortcut::new(ctrl, egui::Key::S),
+                EguiShortcutScope::Global,
+                Save,
+            )
+            .add(
+                egui::KeyboardShortcut::new(ctrl | egui::Modifiers::SHIFT, egui::Key::S),
+                EguiShortcutScope::Context(context),
+                SaveAs,
+            )
+            .add(
+                egui::KeyboardShortcut::new(ctrl, egui::Key::P),
+                EguiShortcutScope::NotTyping,
+                OpenCommandPalette,
+            );
+        let send = |app: &mut App, events: &[(Entity, egui::Event)]| {
+            for (context, event) in events {
+                app.world_mut()
+                    .get_mut::<EguiInput>(*context)
+                    .unwrap()
+                    .events
+                    .push(event.clone());
+            }
+            app.update();
+            (
+                message_count::<Save>(app),
+                message_count::<SaveAs>(app),
+                message_count::<OpenCommandPalette>(app),
+            )
+        };
+        let remaining_events = |app: &mut App, context| {
+            std::mem::take(
+                &mut app
+                    .world_mut()
+                    .get_mut::<EguiInput>(context)
+                    .unwrap()
+                    .events,
+            )
+        };
+
+        // A key press sent to both contexts fires a global shortcut once, even while typing.
+        let save = key(egui::Key::S, ctrl);
+        assert_eq!(
+            send(&mut app, &[(context, save.clone()), (typing_context, save)]),
+            (1, 0, 0)
+        );
+        assert!(remaining_events(&mut app, context).is_empty());
+        assert!(remaining_events(&mut app, typing_context).is_empty());
+
+        // The more specific shortcut takes priority, context shortcuts fire only in their contexts.
+        let save_as = key(egui::Key::S, ctrl | egui::Modifiers::SHIFT);
+        assert_eq!(
+            send(&mut app, &[(typing_context, save_as.clone())]),
+            (1, 0, 0)
+        );
+        assert_eq!(send(&mut app, &[(context, save_as)]), (0, 1, 0));
+
+        // Shortcuts that require no typing are left to the text field.
+        let open_palette = key(egui::Key::P, ctrl);
+        assert_eq!(
+            send(&mut app, &[(typing_context, open_palette.clone())]),
+            (0, 0, 0)
+        );
+        assert_eq!(
+            remaining_events(&mut app, typing_context),
+            vec![open_palette.clone()]
+        );
+        // The whole window counts as typing.
+        assert_eq!(
+            send(&mut app, &[(window_context, open_palette.clone())]),
+            (0, 0, 0)
+        );
+        assert_eq!(
+            remaining_events(&mut app, window_context),
+            vec![open_palette.clone()]
+        );
+        assert_eq!(send(&mut app, &[(context, open_palette)]), (0, 0, 1));
+    }
+}
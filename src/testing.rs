@@ -0,0 +1,356 @@
+//! A headless harness for testing Egui UI built with Bevy systems.
+//!
+//! [`EguiTestHarness`] builds an [`App`] with [`EguiPlugin`], but without windows, winit or a GPU.
+//! It runs frames with a fixed screen rect, finds widgets through the AccessKit tree that Egui outputs,
+//! and simulates interaction by writing [`EguiInputEvent`] messages at widget rects.
+//!
+//! ```no_run
+//! use bevy_ecs::prelude::*;
+//! use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui, testing::EguiTestHarness};
+//!
+//! #[derive(Resource, Default)]
+//! struct Clicks(u32);
+//!
+//! fn ui_system(mut contexts: EguiContexts, mut clicks: ResMut<Clicks>) -> Result {
+//!     egui::Window::new("Clicks").show(contexts.ctx_mut()?, |ui| {
+//!         if ui.button("Click me").clicked() {
+//!             clicks.0 += 1;
+//!         }
+//!     });
+//!     Ok(())
+//! }
+//!
+//! let mut harness = EguiTestHarness::new();
+//! harness
+//!     .app_mut()
+//!     .init_resource::<Clicks>()
+//!     .add_systems(EguiPrimaryContextPass, ui_system);
+//! harness.run();
+//!
+//! let button = harness.get_by_label("Click me");
+//! harness.click(&button);
+//! assert_eq!(harness.app().world().resource::<Clicks>().0, 1);
+//! ```
+
+use crate::{
+    EguiContext, EguiGlobalSettings, EguiInput, EguiOutput, EguiPlugin, PrimaryEguiContext,
+    input::{EguiInputEvent, EguiInputSource, FocusedNonWindowEguiContext},
+};
+use bevy_app::App;
+use bevy_camera::Camera;
+use bevy_ecs::prelude::*;
+use bevy_input::InputPlugin;
+use bevy_time::{TimePlugin, TimeUpdateStrategy};
+use bevy_window::{ExitCondition, WindowPlugin};
+use egui::accesskit;
+use std::time::Duration;
+
+/// A widget found in the AccessKit tree of the last frame.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EguiTestWidget {
+    /// AccessKit node id (derived from the widget's [`egui::Id`]).
+    pub id: accesskit::NodeId,
+    /// Accessibility role of the widget (a button, a text input, a checkbox, etc.).
+    pub role: accesskit::Role,
+    /// Text of the widget: the label of buttons, checkboxes and other controls, or the text of labels.
+    pub label: Option<String>,
+    /// Current value of the widget (the contents of text inputs, for example).
+    pub value: Option<String>,
+    /// Widget rect in Egui points (they match logical pixels only if [`crate::EguiZoomFactor`] is `1.0`).
+    pub rect: egui::Rect,
+    /// Whether the widget has keyboard focus.
+    pub focused: bool,
+}
+
+/// A headless [`App`] with [`EguiPlugin`] and a single primary Egui context, see the [module docs](self).
+///
+/// Add the systems under test to the [`crate::EguiPrimaryContextPass`] schedule, as in a regular app.
+/// Each frame advances time by a fixed step of 1/60 of a second.
+pub struct EguiTestHarness {
+    app: App,
+    context: Entity,
+    screen_rect: egui::Rect,
+}
+
+impl Default for EguiTestHarness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EguiTestHarness {
+    /// Creates a harness with an 800x600 screen.
+    pub fn new() -> Self {
+        Self::with_screen_size(egui::vec2(800.0, 600.0))
+    }
+
+    /// Creates a harness with a screen of the given size (in Egui points, see [`egui::RawInput::screen_rect`]).
+    pub fn with_screen_size(size: egui::Vec2) -> Self {
+        let mut app = App::new();
+        #[cfg(feature = "render")]
+        {
+            app.init_resource::<bevy_asset::Assets<bevy_image::Image>>()
+                .init_resource::<bevy_asset::Assets<bevy_shader::Shader>>()
+                .add_message::<bevy_asset::AssetEvent<bevy_image::Image>>();
+        }
+        app.add_plugins((
+            TimePlugin,
+            WindowPlugin {
+                primary_window: None,
+                exit_condition: ExitCondition::DontExit,
+                close_when_requested: false,
+                ..Default::default()
+            },
+            InputPlugin,
+        ));
+        #[cfg(feature = "accesskit")]
+        app.add_plugins(bevy_a11y::AccessibilityPlugin);
+        app.add_plugins(EguiPlugin::default());
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 60.0,
+        )));
+        app.world_mut()
+            .resource_mut::<EguiGlobalSettings>()
+            .auto_create_primary_context = false;
+
+        let mut context = EguiContext::default();
+        context.get_mut().enable_accesskit();
+        let context = app
+            .world_mut()
+            .spawn((context, PrimaryEguiContext, Camera::default()))
+            .id();
+        // There's no window to take the focus from, so the context is focused permanently.
+        app.insert_resource(FocusedNonWindowEguiContext(context));
+
+        Self {
+            app,
+            context,
+            screen_rect: egui::Rect::from_min_size(egui::Pos2::ZERO, size),
+        }
+    }
+
+    /// The underlying app, use it to inspect the ECS state.
+    pub fn app(&self) -> &App {
+        &self.app
+    }
+
+    /// The underlying app, use it to add systems and resources.
+    pub fn app_mut(&mut self) -> &mut App {
+        &mut self.app
+    }
+
+    /// The Egui context entity.
+    pub fn context(&self) -> Entity {
+        self.context
+    }
+
+    /// Runs a single frame.
+    pub fn run(&mut self) {
+        self.app
+            .world_mut()
+            .get_mut::<EguiInput>(self.context)
+            .expect("the harness context has been despawned")
+            .screen_rect = Some(self.screen_rect);
+        self.app.update();
+    }
+
+    /// Runs the given number of frames.
+    pub fn run_frames(&mut self, count: usize) {
+        for _ in 0..count {
+            self.run();
+        }
+    }
+
+    /// Writes an [`EguiInputEvent`] for the context, it'll be processed during the next frame.
+    pub fn write_event(&mut self, event: egui::Event) {
+        let context = self.context;
+        self.app.world_mut().write_message(EguiInputEvent {
+            context,
+            source: EguiInputSource::Custom,
+            event,
+        });
+    }
+
+    /// Returns all widgets of the last frame that have a role (layout containers are skipped).
+    pub fn widgets(&self) -> Vec<EguiTestWidget> {
+        let output = self
+            .app
+            .world()
+            .get::<EguiOutput>(self.context)
+            .expect("the harness context has been despawned");
+        let Some(update) = &output.platform_output.accesskit_update else {
+            return Vec::new();
+        };
+        update
+            .nodes
+            .iter()
+            .filter(|(_, node)| node.role() != accesskit::Role::GenericContainer)
+            .filter_map(|(id, node)| {
+                let bounds = node.bounds()?;
+                let label = node.label().or_else(|| {
+                    (node.role() == accesskit::Role::Label)
+                        .then(|| node.value())
+                        .flatten()
+                });
+                Some(EguiTestWidget {
+                    id: *id,
+                    role: node.role(),
+                    label: label.map(str::to_owned),
+                    value: node.value().map(str::to_owned),
+                    rect: egui::Rect::from_min_max(
+                        egui::pos2(bounds.x0 as f32, bounds.y0 as f32),
+                        egui::pos2(bounds.x1 as f32, bounds.y1 as f32),
+                    ),
+                    focused: update.focus == *id,
+                })
+            })
+            .collect()
+    }
+
+    /// Finds a widget by its label.
+    pub fn query_by_label(&self, label: &str) -> Option<EguiTestWidget> {
+        self.widgets()
+            .into_iter()
+            .find(|widget| widget.label.as_deref() == Some(label))
+    }
+
+    /// Finds a widget by its label.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there's no such widget.
+    pub fn get_by_label(&self, label: &str) -> EguiTestWidget {
+        self.query_by_label(label)
+            .unwrap_or_else(|| panic!("no widget labeled {label:?}"))
+    }
+
+    /// Finds all widgets with the given role.
+    pub fn query_all_by_role(&self, role: accesskit::Role) -> Vec<EguiTestWidget> {
+        self.widgets()
+            .into_iter()
+            .filter(|widget| widget.role == role)
+            .collect()
+    }
+
+    /// Finds the only widget with the given role.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no widgets with the role, or there's more than one.
+    pub fn get_by_role(&self, role: accesskit::Role) -> EguiTestWidget {
+        let mut widgets = self.query_all_by_role(role);
+        assert_eq!(
+            widgets.len(),
+            1,
+            "expected exactly one widget with the {role:?} role"
+        );
+        widgets.remove(0)
+    }
+
+    /// Clicks the center of the widget with the primary button,
+    /// running a frame for each step: hovering the widget, pressing and releasing the button.
+    pub fn click(&mut self, widget: &EguiTestWidget) {
+        let pos = widget.rect.center();
+        self.write_event(egui::Event::PointerMoved(pos));
+        self.run();
+        self.write_event(pointer_button(pos, true));
+        self.run();
+        self.write_event(pointer_button(pos, false));
+        self.run();
+    }
+
+    /// Clicks the widget to focus it and types the text.
+    pub fn type_text(&mut self, widget: &EguiTestWidget, text: &str) {
+        self.click(widget);
+        self.write_event(egui::Event::Text(text.to_owned()));
+        self.run();
+    }
+
+    /// Presses and releases the key in a single frame.
+    pub fn press_key(&mut self, key: egui::Key) {
+        for pressed in [true, false] {
+            self.write_event(egui::Event::Key {
+                key,
+                physical_key: None,
+                pressed,
+                repeat: false,
+                modifiers: egui::Modifiers::NONE,
+            });
+        }
+        self.run();
+    }
+
+    /// Drags the widget from its center by `delta`,
+    /// running a frame for each step: hovering the widget, pressing the button, moving the pointer and releasing the button.
+    pub fn drag(&mut self, widget: &EguiTestWidget, delta: egui::Vec2) {
+        let from = widget.rect.center();
+        let to = from + delta;
+        self.write_event(egui::Event::PointerMoved(from));
+        self.run();
+        self.write_event(pointer_button(from, true));
+        self.run();
+        self.write_event(egui::Event::PointerMoved(to));
+        self.run();
+        self.write_event(pointer_button(to, false));
+        self.run();
+    }
+}
+
+fn pointer_button(pos: egui::Pos2, pressed: bool) -> egui::Event {
+    egui::Event::PointerButton {
+        pos,
+        button: egui::PointerButton::Primary,
+        pressed,
+        modifiers: egui::Modifiers::NONE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EguiContexts, EguiPrimaryContextPass};
+
+    #[derive(Resource, Default)]
+    struct TestState {
+        clicks: u32,
+        text: String,
+        value: f32,
+    }
+
+    fn ui_system(mut contexts: EguiContexts, mut state: ResMut<TestState>) -> Result {
+        egui::Window::new("Test").show(contexts.ctx_mut()?, |ui| {
+            if ui.button("Click me").clicked() {
+                state.clicks += 1;
+            }
+            ui.text_edit_singleline(&mut state.text);
+            ui.add(egui::DragValue::new(&mut state.value).speed(1.0));
+        });
+        Ok(())
+    }
+
+    #[test]
+    fn harness_finds_and_interacts_with_widgets() {
+        let mut harness = EguiTestHarness::new();
+        harness
+            .app_mut()
+            .init_resource::<TestState>()
+            .add_systems(EguiPrimaryContextPass, ui_system);
+        harness.run();
+        let button = harness.get_by_label("Click me");
+        assert_eq!(button.role, accesskit::Role::Button);
+        assert!(harness.screen_rect.contains_rect(button.rect));
+        harness.click(&button);
+        assert_eq!(harness.app().world().resource::<TestState>().clicks, 1);
+
+        let text_input = harness.get_by_role(accesskit::Role::TextInput);
+        harness.type_text(&text_input, "hello");
+        assert_eq!(harness.app().world().resource::<TestState>().text, "hello");
+        let text_input = harness.get_by_role(accesskit::Role::TextInput);
+        assert!(text_input.focused);
+        assert_eq!(text_input.value.as_deref(), Some("hello"));
+
+        let drag_value = harness.get_by_role(accesskit::Role::SpinButton);
+        harness.drag(&drag_value, egui::vec2(10.0, 0.0));
+        assert_eq!(harness.app().world().resource::<TestState>().value, 10.0);
+    }
+}
@@ -3,7 +3,7 @@
 
 use crate::{
     EguiContextSettings, EguiInput, EguiOutput, EventClosure, SubscribedEvents,
-    input::{EguiInputEvent, FocusedNonWindowEguiContext},
+    input::{EguiInputEvent, EguiInputSource, FocusedNonWindowEguiContext},
 };
 use bevy_ecs::prelude::*;
 use bevy_log as log;
@@ -109,7 +109,11 @@ pub fn write_text_agent_channel_events_system(
 
         while let Ok(event) = channel.receiver.try_recv() {
             redraw = true;
-            egui_input_writer.write(EguiInputEvent { context, event });
+            egui_input_writer.write(EguiInputEvent {
+                context,
+                source: EguiInputSource::TextAgentChannel,
+                event,
+            });
         }
         if redraw {
             request_redraw_writer.write(RequestRedraw);
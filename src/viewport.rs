@@ -0,0 +1,551 @@
+use crate::{
+    EguiContext, EguiContextSettings, EguiFullOutput, EguiInput, EguiOutput, EguiZoomFactor,
+    input::WindowToEguiContextMap, output::apply_viewport_command,
+};
+use bevy_camera::{Camera2d, RenderTarget};
+use bevy_ecs::{prelude::*, system::SystemParam};
+use bevy_platform::collections::{HashMap, HashSet};
+use bevy_window::{Window, WindowCloseRequested, WindowRef};
+use std::{
+    cell::Cell,
+    sync::{Arc, Mutex},
+};
+
+/// Stores context entities spawned for the native viewports of an Egui context
+/// (see [`EguiContextSettings::enable_native_viewports`]).
+///
+/// The component is inserted into a context entity that renders the [`egui::ViewportId::ROOT`] viewport.
+#[derive(Component, Clone, Debug, Default)]
+pub struct EguiViewports(pub HashMap<egui::ViewportId, Entity>);
+
+/// Marks a context entity that renders a non-root Egui viewport into its own [`Window`].
+///
+/// These entities are spawned and despawned by [`manage_egui_viewports_system`],
+/// they share the same [`egui::Context`] with the [`EguiViewport::root_context`] entity.
+#[derive(Component, Clone)]
+pub struct EguiViewport {
+    /// The context entity rendering the [`egui::ViewportId::ROOT`] viewport.
+    pub root_context: Entity,
+    /// The window entity the viewport is rendered to.
+    pub window: Entity,
+    /// Ids of the viewport and its parent.
+    pub ids: egui::ViewportIdPair,
+    /// Either [`egui::ViewportClass::Immediate`] or [`egui::ViewportClass::Deferred`].
+    pub class: egui::ViewportClass,
+    builder: egui::ViewportBuilder,
+    viewport_ui_cb: Option<Arc<egui::DeferredViewportUiCallback>>,
+}
+
+/// Input and output of immediate viewport passes, which run in the middle of their parent's pass.
+///
+/// Lives in the [`egui::Context`] temp data, so that the immediate viewport renderer can access it.
+#[derive(Clone, Default)]
+struct ImmediateViewportPasses(Arc<Mutex<ImmediateViewportPassesInner>>);
+
+#[derive(Default)]
+struct ImmediateViewportPassesInner {
+    inputs: HashMap<egui::ViewportId, egui::RawInput>,
+    outputs: HashMap<egui::ViewportId, egui::FullOutput>,
+}
+
+impl ImmediateViewportPasses {
+    fn get(ctx: &egui::Context) -> Self {
+        ctx.data_mut(|data| {
+            data.get_temp_mut_or_default::<Self>(egui::Id::new("bevy_egui_immediate_viewports"))
+                .clone()
+        })
+    }
+
+    fn take_output(&self, viewport_id: egui::ViewportId) -> Option<egui::FullOutput> {
+        self.0.lock().unwrap().outputs.remove(&viewport_id)
+    }
+}
+
+thread_local! {
+    static IMMEDIATE_VIEWPORT_RENDERER_INSTALLED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Installs the [`egui::Context::set_immediate_viewport_renderer`] callback for the current thread.
+///
+/// Egui stores the renderer in a thread-local variable, so it needs to be installed on the thread
+/// that shows immediate viewports. [`crate::run_egui_context_pass_loop_system`] installs it before running
+/// the schedule of a context with [`EguiContextSettings::enable_native_viewports`], and runs the schedule
+/// on the same thread. Contexts with [`EguiContextSettings::run_manually`] need to call this function
+/// before showing immediate viewports, otherwise they are embedded.
+pub fn install_immediate_viewport_renderer() {
+    IMMEDIATE_VIEWPORT_RENDERER_INSTALLED.with(|installed| {
+        if !installed.replace(true) {
+            egui::Context::set_immediate_viewport_renderer(render_immediate_viewport);
+        }
+    });
+}
+
+fn render_immediate_viewport(ctx: &egui::Context, mut viewport: egui::ImmediateViewport<'_>) {
+    let passes = ImmediateViewportPasses::get(ctx);
+    let ids = viewport.ids;
+
+    let mut input = passes
+        .0
+        .lock()
+        .unwrap()
+        .inputs
+        .get_mut(&ids.this)
+        .map(egui::RawInput::take)
+        .unwrap_or_default();
+    input.viewport_id = ids.this;
+    input.viewports.entry(ids.this).or_default().parent = Some(ids.parent);
+
+    let output = ctx.run_ui(input, |ui| (viewport.viewport_ui_cb)(ui));
+    let mut passes = passes.0.lock().unwrap();
+    match passes.outputs.get_mut(&ids.this) {
+        Some(previous_output) => previous_output.append(output),
+        None => {
+            passes.outputs.insert(ids.this, output);
+        }
+    }
+}
+
+/// A helper [`SystemParam`] to look up windows and contexts rendering the viewports of the same [`egui::Context`].
+#[derive(SystemParam)]
+pub struct EguiViewportLinks<'w, 's> {
+    viewports: Query<'w, 's, &'static EguiViewports>,
+    viewport_contexts: Query<'w, 's, &'static EguiViewport>,
+    window_to_egui_context_map: Res<'w, WindowToEguiContextMap>,
+}
+
+impl EguiViewportLinks<'_, '_> {
+    /// Returns the context entity rendering the [`egui::ViewportId::ROOT`] viewport of the same [`egui::Context`].
+    pub fn root_context(&self, context: Entity) -> Entity {
+        self.viewport_contexts
+            .get(context)
+            .map_or(context, |viewport| viewport.root_context)
+    }
+
+    /// Returns all the context entities sharing the same [`egui::Context`] (including the passed one).
+    pub fn linked_contexts(&self, context: Entity) -> Vec<Entity> {
+        let root_context = self.root_context(context);
+        let mut contexts = vec![root_context];
+        if let Ok(viewports) = self.viewports.get(root_context) {
+            contexts.extend(viewports.0.values().copied());
+        }
+        contexts
+    }
+
+    /// Returns the context entity that renders a viewport.
+    pub fn viewport_context(
+        &self,
+        context: Entity,
+        viewport_id: egui::ViewportId,
+    ) -> Option<Entity> {
+        let root_context = self.root_context(context);
+        if viewport_id == egui::ViewportId::ROOT {
+            return Some(root_context);
+        }
+        self.viewports
+            .get(root_context)
+            .ok()?
+            .0
+            .get(&viewport_id)
+            .copied()
+    }
+
+    /// Returns the window entity that a viewport is rendered to.
+    pub fn viewport_window(
+        &self,
+        context: Entity,
+        viewport_id: egui::ViewportId,
+    ) -> Option<Entity> {
+        let viewport_context = self.viewport_context(context, viewport_id)?;
+        match self.viewport_contexts.get(viewport_context) {
+            Ok(viewport) => Some(viewport.window),
+            Err(_) => self
+                .window_to_egui_context_map
+                .context_to_window
+                .get(&viewport_context)
+                .copied(),
+        }
+    }
+}
+
+/// Toggles [`egui::Context::embed_viewports`] according to [`EguiContextSettings::enable_native_viewports`].
+pub fn setup_native_viewports_system(
+    mut commands: Commands,
+    mut contexts: Query<
+        (
+            Entity,
+            &mut EguiContext,
+            &EguiContextSettings,
+            Has<EguiViewports>,
+        ),
+        (Changed<EguiContextSettings>, Without<EguiViewport>),
+    >,
+) {
+    for (entity, mut context, settings, has_viewports) in &mut contexts {
+        let ctx = context.get_mut();
+        if ctx.embed_viewports() == settings.enable_native_viewports {
+            ctx.set_embed_viewports(!settings.enable_native_viewports);
+        }
+        if settings.enable_native_viewports && !has_viewports {
+            commands.entity(entity).insert(EguiViewports::default());
+        }
+    }
+}
+
+/// Writes [`egui::ViewportEvent::Close`] for viewports which windows were requested to close,
+/// and passes input of immediate viewports to the immediate viewport renderer.
+pub fn begin_viewport_passes_system(
+    mut window_close_requested_reader: MessageReader<WindowCloseRequested>,
+    mut viewport_contexts: Query<(&EguiViewport, &mut EguiContext, &mut EguiInput)>,
+) {
+    let close_requested: HashSet<Entity> = window_close_requested_reader
+        .read()
+        .map(|message| message.window)
+        .collect();
+
+    for (viewport, mut context, mut egui_input) in &mut viewport_contexts {
+        if close_requested.contains(&viewport.window) {
+            egui_input
+                .viewports
+                .entry(viewport.ids.this)
+                .or_default()
+                .events
+                .push(egui::ViewportEvent::Close);
+        }
+
+        if viewport.class == egui::ViewportClass::Immediate {
+            let input = egui_input.take();
+            ImmediateViewportPasses::get(context.get_mut())
+                .0
+                .lock()
+                .unwrap()
+                .inputs
+                .insert(viewport.ids.this, input);
+        }
+    }
+}
+
+/// Spawns a [`Window`] and a context entity for each viewport reported in [`egui::FullOutput::viewport_output`],
+/// runs passes of deferred viewports, and despawns windows of the viewports that Egui no longer shows.
+///
+/// Viewport windows closed by a user aren't re-created until the next frame, which gives
+/// the application a chance to react to [`egui::ViewportInfo::close_requested`].
+pub fn manage_egui_viewports_system(
+    mut commands: Commands,
+    mut root_contexts: Query<
+        (
+            Entity,
+            &mut EguiContext,
+            &mut EguiFullOutput,
+            &EguiZoomFactor,
+            &EguiContextSettings,
+            &mut EguiViewports,
+        ),
+        Without<EguiViewport>,
+    >,
+    mut viewport_contexts: Query<
+        (
+            Entity,
+            &mut EguiViewport,
+            &mut EguiInput,
+            &mut EguiFullOutput,
+            &EguiOutput,
+        ),
+        Without<EguiViewports>,
+    >,
+    mut windows: Query<(&mut Window, Option<&mut bevy_window::CursorOptions>)>,
+) {
+    for (
+        root_context,
+        mut context,
+        mut root_full_output,
+        &EguiZoomFactor { zoom_factor },
+        settings,
+        mut viewports,
+    ) in &mut root_contexts
+    {
+        let ctx = context.get_mut().clone();
+        let passes = ImmediateViewportPasses::get(&ctx);
+        let Some(root_full_output) = &mut root_full_output.0 else {
+            continue;
+        };
+        let requested_viewports = root_full_output.viewport_output.clone();
+
+        // Despawn viewports that Egui doesn't show anymore or which windows got closed.
+        let mut closed_viewports = HashSet::new();
+        viewports.0.retain(|viewport_id, &mut viewport_context| {
+            let window = viewport_contexts
+                .get(viewport_context)
+                .ok()
+                .map(|(_, viewport, ..)| viewport.window);
+            let window_exists = window.is_some_and(|window| windows.contains(window));
+            if requested_viewports.contains_key(viewport_id) && window_exists {
+                return true;
+            }
+
+            if !window_exists {
+                closed_viewports.insert(*viewport_id);
+            }
+            if let Some(window) = window {
+                commands.entity(window).try_despawn();
+            }
+            commands.entity(viewport_context).try_despawn();
+            false
+        });
+
+        for (&viewport_id, viewport_output) in &requested_viewports {
+            if viewport_id == egui::ViewportId::ROOT || closed_viewports.contains(&viewport_id) {
+                continue;
+            }
+            let ids =
+                egui::ViewportIdPair::from_self_and_parent(viewport_id, viewport_output.parent);
+
+            // Update existing viewports.
+            if let Some(&viewport_context) = viewports.0.get(&viewport_id)
+                && let Ok((_, mut viewport, mut egui_input, mut egui_full_output, egui_output)) =
+                    viewport_contexts.get_mut(viewport_context)
+            {
+                viewport.ids = ids;
+                viewport.class = viewport_output.class;
+                viewport.viewport_ui_cb = viewport_output.viewport_ui_cb.clone();
+                let (builder_commands, _recreate) =
+                    viewport.builder.patch(viewport_output.builder.clone());
+                if let Ok((mut window, mut cursor_options)) = windows.get_mut(viewport.window) {
+                    for command in builder_commands {
+                        apply_viewport_command(
+                            command,
+                            &mut window,
+                            cursor_options.as_deref_mut(),
+                            zoom_factor,
+                        );
+                    }
+                }
+
+                let output = match (viewport.class, &viewport.viewport_ui_cb) {
+                    (egui::ViewportClass::Deferred, Some(viewport_ui_cb)) => {
+                        let mut input = egui_input.take();
+                        input.viewport_id = viewport_id;
+                        input.viewports.entry(viewport_id).or_default().parent = Some(ids.parent);
+                        Some(ctx.run_ui(input, |ui| viewport_ui_cb(ui)))
+                    }
+                    _ => passes.take_output(viewport_id),
+                };
+                egui_full_output.0 = Some(output.unwrap_or_else(|| egui::FullOutput {
+                    pixels_per_point: egui_output.pixels_per_point,
+                    ..Default::default()
+                }));
+                continue;
+            }
+
+            // Spawn new viewports.
+            let mut window = Window::default();
+            let (builder_commands, _recreate) =
+                egui::ViewportBuilder::default().patch(viewport_output.builder.clone());
+            for command in builder_commands {
+                apply_viewport_command(command, &mut window, None, zoom_factor);
+            }
+            let window = commands.spawn(window).id();
+
+            let initial_output =
+                passes
+                    .take_output(viewport_id)
+                    .unwrap_or_else(|| egui::FullOutput {
+                        pixels_per_point: root_full_output.pixels_per_point,
+                        ..Default::default()
+                    });
+            let viewport_context = commands
+                .spawn((
+                    EguiViewport {
+                        root_context,
+                        window,
+                        ids,
+                        class: viewport_output.class,
+                        builder: viewport_output.builder.clone(),
+                        viewport_ui_cb: viewport_output.viewport_ui_cb.clone(),
+                    },
+                    EguiContext { ctx: ctx.clone() },
+                    EguiContextSettings {
+                        run_manually: true,
+                        enable_native_viewports: false,
+                        ..settings.clone()
+                    },
+                    EguiInput(egui::RawInput {
+                        viewport_id,
+                        ..Default::default()
+                    }),
+                    EguiFullOutput(Some(initial_output)),
+                    Camera2d,
+                    RenderTarget::Window(WindowRef::Entity(window)),
+                ))
+                .id();
+            viewports.0.insert(viewport_id, viewport_context);
+        }
+
+        // Texture updates of the immediate viewports that didn't get rendered still need to be applied.
+        let mut passes = passes.0.lock().unwrap();
+        passes.inputs.retain(|id, _| viewports.0.contains_key(id));
+        for (_, output) in passes.outputs.drain() {
+            root_full_output
+                .textures_delta
+                .append(output.textures_delta);
+        }
+    }
+
+    // Despawn viewports of removed root contexts.
+    for (viewport_context, viewport, ..) in &viewport_contexts {
+        if !root_contexts.contains(viewport.root_context) {
+            commands.entity(viewport.window).try_despawn();
+            commands.entity(viewport_context).try_despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::{App, Update};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn app_with_viewports_system() -> (App, Entity) {
+        let mut app = App::new();
+        app.add_message::<WindowCloseRequested>()
+            .add_systems(Update, manage_egui_viewports_system);
+
+        let context = app
+            .world_mut()
+            .spawn((
+                EguiContext::default(),
+                EguiContextSettings {
+                    enable_native_viewports: true,
+                    ..Default::default()
+                },
+                EguiViewports::default(),
+            ))
+            .id();
+        let mut egui_context = app.world_mut().get_mut::<EguiContext>(context).unwrap();
+        egui_context.get_mut().set_embed_viewports(false);
+        (app, context)
+    }
+
+    fn run_root_pass(app: &mut App, context: Entity, ui: impl FnMut(&mut egui::Ui)) {
+        let mut egui_context = app.world_mut().get_mut::<EguiContext>(context).unwrap();
+        let mut full_output = egui_context.get_mut().run_ui(Default::default(), ui);
+        full_output.textures_delta.clear();
+        app.world_mut()
+            .get_mut::<EguiFullOutput>(context)
+            .unwrap()
+            .0 = Some(full_output);
+    }
+
+    #[test]
+    fn deferred_viewport_spawns_and_despawns_window() {
+        let (mut app, context) = app_with_viewports_system();
+        let viewport_id = egui::ViewportId::from_hash_of("deferred");
+        let ui_calls = Arc::new(AtomicUsize::new(0));
+        let show_viewport = |ui: &mut egui::Ui| {
+            let ui_calls = ui_calls.clone();
+            ui.ctx().show_viewport_deferred(
+                viewport_id,
+                egui::ViewportBuilder::default().with_title("Torn off"),
+                move |_ui, class| {
+                    assert!(class == egui::ViewportClass::Deferred);
+                    ui_calls.fetch_add(1, Ordering::Relaxed);
+                },
+            );
+        };
+
+        run_root_pass(&mut app, context, show_viewport);
+        app.update();
+
+        let viewport_context = app.world().get::<EguiViewports>(context).unwrap().0[&viewport_id];
+        let viewport = app.world().get::<EguiViewport>(viewport_context).unwrap();
+        assert_eq!(viewport.root_context, context);
+        assert_eq!(viewport.ids.this, viewport_id);
+        let window = viewport.window;
+        assert_eq!(app.world().get::<Window>(window).unwrap().title, "Torn off");
+        assert_eq!(
+            app.world()
+                .get::<EguiInput>(viewport_context)
+                .unwrap()
+                .viewport_id,
+            viewport_id
+        );
+
+        // Deferred viewports are rendered by the plugin once their windows exist.
+        run_root_pass(&mut app, context, show_viewport);
+        app.update();
+        assert_eq!(ui_calls.load(Ordering::Relaxed), 1);
+        app.world_mut()
+            .get_mut::<EguiFullOutput>(viewport_context)
+            .unwrap()
+            .0
+            .take()
+            .unwrap()
+            .textures_delta
+            .clear();
+
+        run_root_pass(&mut app, context, |_| {});
+        app.update();
+        assert!(
+            app.world()
+                .get::<EguiViewports>(context)
+                .unwrap()
+                .0
+                .is_empty()
+        );
+        assert!(app.world().get_entity(viewport_context).is_err());
+        assert!(app.world().get_entity(window).is_err());
+    }
+
+    #[test]
+    fn immediate_viewports_are_rendered_by_context_schedules() {
+        use bevy_ecs::schedule::ScheduleLabel;
+
+        #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+        struct ViewportPass;
+
+        let classes = Arc::new(Mutex::new(Vec::new()));
+        let mut app = App::new();
+        app.add_systems(ViewportPass, {
+            let classes = classes.clone();
+            move |mut contexts: Query<&mut EguiContext>| {
+                for mut context in &mut contexts {
+                    context.get_mut().show_viewport_immediate(
+                        egui::ViewportId::from_hash_of("immediate"),
+                        egui::ViewportBuilder::default(),
+                        |_ui, class| classes.lock().unwrap().push(class),
+                    );
+                }
+            }
+        })
+        .add_systems(
+            Update,
+            (
+                setup_native_viewports_system,
+                crate::run_egui_context_pass_loop_system,
+            )
+                .chain(),
+        );
+        let context = app
+            .world_mut()
+            .spawn((
+                EguiContext::default(),
+                EguiContextSettings {
+                    enable_native_viewports: true,
+                    ..Default::default()
+                },
+                crate::EguiSchedule(ViewportPass.intern()),
+            ))
+            .id();
+
+        app.update();
+        assert!(classes.lock().unwrap()[..] == [egui::ViewportClass::Immediate]);
+
+        let mut egui_context = app.world_mut().get_mut::<EguiContext>(context).unwrap();
+        let passes = ImmediateViewportPasses::get(egui_context.get_mut());
+        for (_, mut output) in passes.0.lock().unwrap().outputs.drain() {
+            output.textures_delta.clear();
+        }
+        let mut full_output = app.world_mut().get_mut::<EguiFullOutput>(context).unwrap();
+        full_output.0.take().unwrap().textures_delta.clear();
+    }
+}
@@ -0,0 +1,348 @@
+use crate::{
+    EguiContext, EguiContextSettings, EguiInput,
+    input::{
+        EguiContextPointerPosition, EguiInputEvent, EguiInputSource, ModifierKeysState,
+        WindowModifierKeysState, WindowToEguiContextMap, context_modifiers,
+    },
+};
+use bevy_ecs::prelude::*;
+use bevy_input::gamepad::{Gamepad, GamepadAxis, GamepadButton, GamepadButtonStateChangedEvent};
+use bevy_math::Vec2;
+use bevy_platform::collections::HashMap;
+use bevy_time::{Real, Time};
+
+/// Add this component to an [`EguiContext`] entity to control its pointer with a gamepad.
+///
+/// The cursor is moved by a stick (the left one by default) with acceleration, a gamepad button emits
+/// [`egui::PointerButton::Primary`] presses, and the cursor itself is painted by Egui on top of everything else.
+/// This is useful for menus built for mouse, when the OS cursor is hidden or unavailable.
+///
+/// Note that [`crate::input::EguiGamepadMapping`] maps the same stick and button to focus navigation by default,
+/// consider disabling [`crate::input::EguiGamepadMapping::left_stick_navigation`] and removing the button mapping.
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct EguiGamepadVirtualCursor {
+    /// The gamepad that controls the cursor, [`None`] means any gamepad.
+    pub gamepad: Option<Entity>,
+    /// Axis that moves the cursor horizontally.
+    pub x_axis: GamepadAxis,
+    /// Axis that moves the cursor vertically.
+    pub y_axis: GamepadAxis,
+    /// Button that emits primary pointer button events.
+    pub button: GamepadButton,
+    /// The stick deflection (from `0.0` to `1.0`) below which it's ignored.
+    pub dead_zone: f32,
+    /// Cursor speed in points per second at full stick deflection, when the stick starts moving.
+    pub speed: f32,
+    /// Speed gain in points per second squared while the stick is held.
+    pub acceleration: f32,
+    /// Maximum cursor speed in points per second.
+    pub max_speed: f32,
+    /// Whether the cursor is painted.
+    pub visible: bool,
+    /// Size of the painted cursor in points.
+    pub size: f32,
+}
+
+impl Default for EguiGamepadVirtualCursor {
+    fn default() -> Self {
+        Self {
+            gamepad: None,
+            x_axis: GamepadAxis::LeftStickX,
+            y_axis: GamepadAxis::LeftStickY,
+            button: GamepadButton::South,
+            dead_zone: 0.15,
+            speed: 300.0,
+            acceleration: 1200.0,
+            max_speed: 1500.0,
+            visible: true,
+            size: 16.0,
+        }
+    }
+}
+
+/// Moves [`EguiContextPointerPosition`] of contexts with the [`EguiGamepadVirtualCursor`] component,
+/// and wraps cursor movement and button presses into [`EguiInputEvent`].
+#[allow(clippy::too_many_arguments)]
+pub fn write_gamepad_virtual_cursor_messages_system(
+    mut cursors: Query<(
+        Entity,
+        &EguiGamepadVirtualCursor,
+        &mut EguiContext,
+        &mut EguiContextPointerPosition,
+        &EguiInput,
+        &EguiContextSettings,
+    )>,
+    mut removed_cursors: RemovedComponents<EguiGamepadVirtualCursor>,
+    mut contexts_without_cursor: Query<&mut EguiContext, Without<EguiGamepadVirtualCursor>>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    mut gamepad_button_reader: MessageReader<GamepadButtonStateChangedEvent>,
+    mut held_durations: Local<HashMap<Entity, f32>>,
+    modifier_keys_state: Res<ModifierKeysState>,
+    window_modifier_keys_state: Res<WindowModifierKeysState>,
+    egui_context_to_window: Res<WindowToEguiContextMap>,
+    time: Res<Time<Real>>,
+    mut egui_input_message_writer: MessageWriter<EguiInputEvent>,
+) {
+    for entity in removed_cursors.read() {
+        held_durations.remove(&entity);
+        if let Ok(mut context) = contexts_without_cursor.get_mut(entity) {
+            context
+                .get_mut()
+                .with_plugin(|painter: &mut VirtualCursorPainter| {
+                    painter.position = None;
+                });
+        }
+    }
+
+    let button_messages: Vec<_> = gamepad_button_reader.read().collect();
+    let dt = time.delta_secs();
+
+    for (entity, cursor, mut context, mut pointer_position, egui_input, context_settings) in
+        &mut cursors
+    {
+        if !context_settings
+            .input_system_settings
+            .run_write_gamepad_virtual_cursor_messages_system
+        {
+            held_durations.remove(&entity);
+            continue;
+        }
+
+        let controls = |gamepad: Entity| cursor.gamepad.is_none_or(|g| g == gamepad);
+
+        let stick = gamepads
+            .iter()
+            .filter(|(gamepad, _)| controls(*gamepad))
+            .map(|(_, gamepad)| {
+                Vec2::new(
+                    gamepad.get(cursor.x_axis).unwrap_or(0.0),
+                    gamepad.get(cursor.y_axis).unwrap_or(0.0),
+                )
+            })
+            .sum::<Vec2>()
+            .clamp_length_max(1.0);
+
+        let held_duration = held_durations.entry(entity).or_default();
+        if stick.length() > cursor.dead_zone {
+            *held_duration += dt;
+            let speed = (cursor.speed + cursor.acceleration * *held_duration).min(cursor.max_speed);
+            let mut position =
+                pointer_position.position + egui::vec2(stick.x, -stick.y) * speed * dt;
+            if let Some(screen_rect) = egui_input.screen_rect {
+                position = screen_rect.clamp(position);
+            }
+            pointer_position.position = position;
+            egui_input_message_writer.write(EguiInputEvent {
+                context: entity,
+                source: EguiInputSource::GamepadVirtualCursor,
+                event: egui::Event::PointerMoved(position),
+            });
+        } else {
+            *held_duration = 0.0;
+        }
+
+        let modifiers = context_modifiers(
+            entity,
+            &egui_context_to_window,
+            &window_modifier_keys_state,
+            &modifier_keys_state,
+        );
+        for message in &button_messages {
+            if message.button != cursor.button || !controls(message.entity) {
+                continue;
+            }
+            egui_input_message_writer.write(EguiInputEvent {
+                context: entity,
+                source: EguiInputSource::GamepadVirtualCursor,
+                event: egui::Event::PointerButton {
+                    pos: pointer_position.position,
+                    button: egui::PointerButton::Primary,
+                    pressed: message.state.is_pressed(),
+                    modifiers,
+                },
+            });
+        }
+
+        let ctx = context.get_mut();
+        ctx.add_plugin(VirtualCursorPainter::default());
+        ctx.with_plugin(|painter: &mut VirtualCursorPainter| {
+            painter.position = cursor.visible.then_some(pointer_position.position);
+            painter.size = cursor.size;
+        });
+    }
+}
+
+/// Paints the virtual cursor at the end of each pass.
+#[derive(Default)]
+struct VirtualCursorPainter {
+    position: Option<egui::Pos2>,
+    size: f32,
+}
+
+impl egui::Plugin for VirtualCursorPainter {
+    fn debug_name(&self) -> &'static str {
+        "bevy_egui_virtual_cursor"
+    }
+
+    fn on_end_pass(&mut self, ui: &mut egui::Ui) {
+        let Some(position) = self.position else {
+            return;
+        };
+        if ui.ctx().viewport_id() != egui::ViewportId::ROOT {
+            return;
+        }
+
+        let painter = ui.ctx().layer_painter(egui::LayerId::new(
+            egui::Order::Debug,
+            egui::Id::new("bevy_egui_virtual_cursor"),
+        ));
+        let size = self.size;
+        painter.add(egui::Shape::convex_polygon(
+            vec![
+                position,
+                position + egui::vec2(0.0, size),
+                position + egui::vec2(size * 0.7, size * 0.7),
+            ],
+            egui::Color32::WHITE,
+            egui::Stroke::new(1.0, egui::Color32::BLACK),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::{App, Update};
+    use bevy_ecs::message::Messages;
+    use bevy_input::ButtonState;
+    use std::time::Duration;
+
+    #[test]
+    fn gamepad_moves_virtual_cursor_and_clicks() {
+        let mut app = App::new();
+        app.add_message::<GamepadButtonStateChangedEvent>()
+            .add_message::<EguiInputEvent>()
+            .init_resource::<ModifierKeysState>()
+            .init_resource::<WindowModifierKeysState>()
+            .init_resource::<WindowToEguiContextMap>()
+            .init_resource::<Time<Real>>()
+            .add_systems(Update, write_gamepad_virtual_cursor_messages_system);
+        let context = app
+            .world_mut()
+            .spawn((
+                EguiContext::default(),
+                EguiGamepadVirtualCursor {
+                    acceleration: 0.0,
+                    ..Default::default()
+                },
+            ))
+            .id();
+        app.world_mut()
+            .get_mut::<EguiInput>(context)
+            .unwrap()
+            .screen_rect = Some(egui::Rect::from_min_size(
+            egui::Pos2::ZERO,
+            egui::vec2(100.0, 100.0),
+        ));
+        app.world_mut()
+            .get_mut::<EguiContextPointerPosition>(context)
+            .unwrap()
+            .position = egui::pos2(90.0, 50.0);
+        // Modifiers are read from the window of the context.
+        let window = app.world_mut().spawn_empty().id();
+        let mut map = app.world_mut().resource_mut::<WindowToEguiContextMap>();
+        map.window_to_contexts
+            .entry(window)
+            .or_default()
+            .insert(context);
+        map.context_to_window.insert(context, window);
+        app.world_mut()
+            .resource_mut::<WindowModifierKeysState>()
+            .get_mut(window)
+            .shift = true;
+        let gamepad = app.world_mut().spawn(Gamepad::default()).id();
+        let set_left_stick = |app: &mut App, x: f32, y: f32| {
+            let mut gamepad = app.world_mut().get_mut::<Gamepad>(gamepad).unwrap();
+            gamepad.analog_mut().set(GamepadAxis::LeftStickX, x);
+            gamepad.analog_mut().set(GamepadAxis::LeftStickY, y);
+        };
+        let pointer_events = |app: &App| {
+            app.world()
+                .resource::<Messages<EguiInputEvent>>()
+                .iter_current_update_messages()
+                .map(|message| {
+                    assert_eq!(message.context, context);
+                    message.event.clone()
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut time = app.world_mut().resource_mut::<Time<Real>>();
+        // The first update doesn't advance the delta.
+        time.update_with_duration(Duration::ZERO);
+        time.update_with_duration(Duration::from_millis(100));
+
+        // Pushing the stick down moves the cursor down.
+        set_left_stick(&mut app, 0.0, -1.0);
+        app.update();
+        assert_eq!(
+            pointer_events(&app),
+            [egui::Event::PointerMoved(egui::pos2(90.0, 80.0))]
+        );
+
+        // The cursor is clamped by the screen rect.
+        set_left_stick(&mut app, 1.0, 0.0);
+        app.world_mut()
+            .write_message(GamepadButtonStateChangedEvent::new(
+                gamepad,
+                GamepadButton::South,
+                ButtonState::Pressed,
+            ));
+        app.update();
+        assert_eq!(
+            pointer_events(&app),
+            [
+                egui::Event::PointerMoved(egui::pos2(100.0, 80.0)),
+                egui::Event::PointerButton {
+                    pos: egui::pos2(100.0, 80.0),
+                    button: egui::PointerButton::Primary,
+                    pressed: true,
+                    modifiers: egui::Modifiers::SHIFT,
+                },
+            ]
+        );
+        assert_eq!(
+            app.world()
+                .get::<EguiContextPointerPosition>(context)
+                .unwrap()
+                .position,
+            egui::pos2(100.0, 80.0)
+        );
+
+        // The cursor is painted on top of the (empty) UI.
+        let ctx = app
+            .world_mut()
+            .get_mut::<EguiContext>(context)
+            .unwrap()
+            .get_mut()
+            .clone();
+        let mut output = ctx.run_ui(egui::RawInput::default(), |_| {});
+        output.textures_delta.clear();
+        assert_eq!(output.shapes.len(), 1);
+
+        // Nothing is written for contexts that disable the system.
+        app.world_mut()
+            .get_mut::<EguiContextSettings>(context)
+            .unwrap()
+            .input_system_settings
+            .run_write_gamepad_virtual_cursor_messages_system = false;
+        set_left_stick(&mut app, 0.0, 1.0);
+        app.world_mut()
+            .write_message(GamepadButtonStateChangedEvent::new(
+                gamepad,
+                GamepadButton::South,
+                ButtonState::Released,
+            ));
+        app.update();
+        assert_eq!(pointer_events(&app), []);
+    }
+}
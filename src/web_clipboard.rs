@@ -1,12 +1,13 @@
 use crate::{
-    EguiClipboard, EguiContext, EguiContextSettings, EventClosure, SubscribedEvents,
-    input::{EguiInputEvent, FocusedNonWindowEguiContext},
+    EguiClipboard, EguiClipboardBackend, EguiContext, EguiContextSettings, EventClosure,
+    SubscribedEvents,
+    input::{EguiInputEvent, EguiInputSource, FocusedNonWindowEguiContext},
     string_from_js_value,
 };
 use bevy_ecs::prelude::*;
 use bevy_log as log;
 use bevy_platform::collections::HashSet;
-use crossbeam_channel::{Receiver, Sender};
+use crossbeam_channel::Sender;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::spawn_local;
 
@@ -16,7 +17,7 @@ pub fn startup_setup_web_events_system(
     mut subscribed_events: NonSendMut<SubscribedEvents>,
 ) {
     let (tx, rx) = crossbeam_channel::unbounded();
-    egui_clipboard.clipboard.event_receiver = Some(rx);
+    egui_clipboard.web_event_receiver = Some(rx);
     setup_clipboard_copy(&mut subscribed_events, tx.clone());
     setup_clipboard_cut(&mut subscribed_events, tx.clone());
     setup_clipboard_paste(&mut subscribed_events, tx);
@@ -62,12 +63,14 @@ pub fn write_web_clipboard_events_system(
                 crate::web_clipboard::WebClipboardEvent::Copy => {
                     egui_input_event_writer.write(EguiInputEvent {
                         context,
+                        source: EguiInputSource::WebClipboard,
                         event: egui::Event::Copy,
                     });
                 }
                 crate::web_clipboard::WebClipboardEvent::Cut => {
                     egui_input_event_writer.write(EguiInputEvent {
                         context,
+                        source: EguiInputSource::WebClipboard,
                         event: egui::Event::Cut,
                     });
                 }
@@ -75,6 +78,7 @@ pub fn write_web_clipboard_events_system(
                     egui_clipboard.set_text_internal(&text);
                     egui_input_event_writer.write(EguiInputEvent {
                         context,
+                        source: EguiInputSource::WebClipboard,
                         event: egui::Event::Paste(text),
                     });
                 }
@@ -85,10 +89,9 @@ pub fn write_web_clipboard_events_system(
     Ok(())
 }
 
-/// Internal implementation of `[crate::EguiClipboard]` for web.
+/// Web implementation of [`EguiClipboardBackend`].
 #[derive(Default)]
 pub struct WebClipboard {
-    event_receiver: Option<Receiver<WebClipboardEvent>>,
     contents: Option<String>,
 }
 
@@ -104,46 +107,29 @@ pub enum WebClipboardEvent {
 }
 
 impl WebClipboard {
-    /// Places the text onto the clipboard.
-    pub fn set_text(&mut self, text: &str) {
-        self.set_text_internal(text);
-        set_clipboard_text(text.to_owned());
-    }
-
     /// Sets the internal buffer of clipboard contents.
     /// This buffer is used to remember the contents of the last `paste` event.
     pub fn set_text_internal(&mut self, text: &str) {
         self.contents = Some(text.to_owned());
     }
+}
 
-    /// Gets clipboard contents. Returns [`None`] if the `copy`/`cut` operation have never been invoked yet,
+impl EguiClipboardBackend for WebClipboard {
+    fn set_text(&mut self, text: &str) {
+        self.set_text_internal(text);
+        set_clipboard_text(text.to_owned());
+    }
+
+    /// Returns [`None`] if the `copy`/`cut` operation have never been invoked yet,
     /// or the `paste` event has never been received yet.
-    pub fn get_text(&mut self) -> Option<String> {
+    fn get_text(&mut self) -> Option<String> {
         self.contents.clone()
     }
 
-    /// Places the image onto the clipboard.
-    pub fn set_image(&mut self, image: &egui::ColorImage) {
+    fn set_image(&mut self, image: &egui::ColorImage) {
         self.contents = None;
         set_clipboard_image(image);
     }
-
-    /// Receives a clipboard event sent by the `copy`/`cut`/`paste` listeners.
-    pub fn try_receive_clipboard_event(&self) -> Option<WebClipboardEvent> {
-        let Some(rx) = &self.event_receiver else {
-            log::error!("Web clipboard event receiver isn't initialized");
-            return None;
-        };
-
-        match rx.try_recv() {
-            Ok(event) => Some(event),
-            Err(crossbeam_channel::TryRecvError::Empty) => None,
-            Err(err @ crossbeam_channel::TryRecvError::Disconnected) => {
-                log::error!("Failed to read a web clipboard event: {err:?}");
-                None
-            }
-        }
-    }
 }
 
 fn setup_clipboard_copy(subscribed_events: &mut SubscribedEvents, tx: Sender<WebClipboardEvent>) {